@@ -145,9 +145,10 @@ class DraftService {
   }
 
   loadContractAbi() {
-    // Load the actual contract ABI from the deployment
+    // Load the contract ABI from the deployment, LateDeliveryAndPenalty unless configured
     const contractPath = path.resolve(
-      "../inkathon/contracts/late-delivery-and-penalty/deployments/late-delivery-and-penalty.json"
+      process.env.CONTRACT_ABI_PATH ||
+        "../inkathon/contracts/late-delivery-and-penalty/deployments/late-delivery-and-penalty.json"
     );
     try {
       const contractData = JSON.parse(fs.readFileSync(contractPath, "utf8"));
@@ -315,7 +316,7 @@ class DraftService {
           const [contractAddress, eventBytes] = event.data;
 
           if (contractAddress.toString() === process.env.CONTRACT_ADDRESS) {
-            this.handleContractEvent(eventBytes);
+            this.handleContractEvent(record, eventBytes);
           }
         }
       });
@@ -325,10 +326,10 @@ class DraftService {
     this.eventUnsubscriber = unsub;
   }
 
-  handleContractEvent(eventBytes) {
+  handleContractEvent(record, eventBytes) {
     try {
-      // Use ink!-specific event decoding (optimized for ink! contracts)
-      const decoded = this.decodeInkEvent(eventBytes);
+      // The ABI identifies the event by its signature topic and decodes its fields
+      const decoded = this.contract.abi.decodeEvent(record);
       logger.info("Event decoding successful");
       this.processDecodedEvent(decoded);
     } catch (decodeError) {
@@ -343,152 +344,38 @@ class DraftService {
   processDecodedEvent(decoded) {
     if (decoded.event.identifier.includes("DraftRequested")) {
       logger.info("Processing DraftRequested event");
-      this.processDraftRequest(decoded.event.args || decoded.args);
+      this.processDraftRequest(this.decodeDraftRequestedArgs(decoded));
     } else {
       logger.info(`Received ${decoded.event.identifier} event`);
     }
   }
 
-  // Simplified ink!-specific event decoder
-  decodeInkEvent(eventBytes) {
-    // Convert to Uint8Array for consistent processing
-    let eventData;
-    if (typeof eventBytes.toHex === "function") {
-      const hexString = eventBytes.toHex();
-      const cleanHex = hexString.startsWith("0x")
-        ? hexString.slice(2)
-        : hexString;
-      eventData = new Uint8Array(
-        cleanHex.match(/.{1,2}/g).map((byte) => parseInt(byte, 16))
-      );
-    } else if (typeof eventBytes.toU8a === "function") {
-      eventData = eventBytes.toU8a();
-    } else {
-      eventData = eventBytes;
-    }
-
-    // Identify event type by length
-    if (eventData.length > 100) {
-      return this.decodeDraftRequestedEvent(eventData);
-    } else if (eventData.length >= 39 && eventData.length <= 41) {
-      return this.decodeLateDeliveryRequestEvent(eventData);
-    } else if (eventData.length >= 8 && eventData.length <= 10) {
-      return this.decodeLateDeliveryResponseEvent(eventData);
-    } else {
-      throw new Error(
-        `Unknown event type with length ${eventData.length}. Expected: 8-10, 39-41, or >100`
-      );
-    }
-  }
-
-  // DraftRequested decoder
-  decodeDraftRequestedEvent(eventData) {
-    let offset = 0;
-
-    // 1. requester (AccountId) - 32 bytes
-    const requesterBytes = eventData.slice(offset, offset + 32);
-    const requester = this.api.registry.createType("AccountId", requesterBytes);
-    offset += 32;
-
-    // 2. request_id (u64) - 8 bytes
-    const requestIdBytes = eventData.slice(offset, offset + 8);
-    const requestId = this.api.registry.createType("u64", requestIdBytes);
-    offset += 8;
-
-    // 3. template_data (String) - SCALE encoded
-    const stringDataStart = eventData.slice(offset);
-    const { value: templateData, bytesRead } =
-      this.decodeScaleString(stringDataStart);
-    offset += bytesRead;
-
-    // 4. timestamp (u64) - 8 bytes
-    const timestampBytes = eventData.slice(offset, offset + 8);
-    const timestamp = this.api.registry.createType("u64", timestampBytes);
-
-    return {
-      event: {
-        identifier:
-          "late_delivery_and_penalty::latedeliveryandpenalty::DraftRequested",
-        args: {
-          requester: requester.toString(),
-          request_id: requestId.toNumber(),
-          template_data: templateData,
-          timestamp: timestamp.toNumber(),
-        },
-      },
-    };
-  }
-
-  // SCALE string decoder
-  decodeScaleString(data) {
-    // Decode compact length
-    const compact = this.api.registry.createType("Compact<u32>", data);
-    const stringLength = compact.toNumber();
-    const bytesRead = compact.encodedLength;
-
-    // Extract string data
-    const stringBytes = data.slice(bytesRead, bytesRead + stringLength);
-    const value = new TextDecoder().decode(stringBytes);
-
-    return {
-      value: value.replace(/\0+$/, "").trim(),
-      bytesRead: bytesRead + stringLength,
-    };
-  }
-
-  // Other event decoders
-  decodeLateDeliveryRequestEvent(eventData) {
-    let offset = 0;
-    const submitterBytes = eventData.slice(offset, offset + 32);
-    const submitter = this.api.registry.createType("AccountId", submitterBytes);
-    offset += 32;
-
-    const requestIdBytes = eventData.slice(offset, offset + 8);
-    const requestId = this.api.registry.createType("u64", requestIdBytes);
-
-    return {
-      event: {
-        identifier:
-          "late_delivery_and_penalty::latedeliveryandpenalty::LateDeliveryAndPenaltyRequestSubmitted",
-        args: {
-          submitter: submitter.toString(),
-          request_id: requestId.toNumber(),
-        },
-      },
-    };
-  }
-
-  decodeLateDeliveryResponseEvent(eventData) {
-    let offset = 0;
-    const requestIdBytes = eventData.slice(offset, offset + 8);
-    const requestId = this.api.registry.createType("u64", requestIdBytes);
-    offset += 8;
-
-    const successByte = eventData[offset];
-    const success = successByte === 1;
+  // Name the decoded DraftRequested fields, which both contracts emit with the same layout
+  decodeDraftRequestedArgs(decoded) {
+    const args = Object.fromEntries(
+      decoded.event.args.map((arg, index) => [arg.name, decoded.args[index]])
+    );
 
     return {
-      event: {
-        identifier:
-          "late_delivery_and_penalty::latedeliveryandpenalty::LateDeliveryAndPenaltyResponseGenerated",
-        args: {
-          request_id: requestId.toNumber(),
-          success: success,
-        },
-      },
+      requester: args.requester.toString(),
+      request_id: args.request_id.toNumber(),
+      template_data: args.template_data.toString(),
+      timestamp: args.timestamp.toNumber(),
+      // DraftFormat variant: Markdown or Pdf
+      output_format: args.output_format.toString() === "Pdf" ? "pdf" : "md",
     };
   }
 
   async processDraftRequest(eventData) {
-    const { requester, request_id, template_data, timestamp } = eventData;
+    const { requester, request_id, template_data, timestamp, output_format } =
+      eventData;
 
     try {
       // Parse template data
       const templateModelData = JSON.parse(template_data);
 
-      // Extract format preference from template data, fallback to env variable
-      const { _outputFormat, ...cleanTemplateData } = templateModelData;
-      const requestedFormat = _outputFormat || this.outputFormat;
+      // Use the format requested on-chain, fallback to env variable
+      const requestedFormat = output_format || this.outputFormat;
 
       logger.info(
         `Processing draft request ${request_id} from ${requester} (format: ${requestedFormat})`
@@ -496,7 +383,7 @@ class DraftService {
 
       // Generate the draft using the template processor
       const draftMarkdown = await this.templateProcessor.draft(
-        cleanTemplateData,
+        templateModelData,
         "markdown",
         { verbose: false }
       );
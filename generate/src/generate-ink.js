@@ -314,8 +314,10 @@ function toInkType(concertoType, isOptional = false, isArray = false) {
       rustType = "bool";
       break;
     case "Double":
+      rustType = "Decimal"; // Fixed-point, from the shared contract-common crate
+      break;
     case "Long":
-      rustType = "u128"; // Use u128 for financial calculations in ink!
+      rustType = "u128";
       break;
     case "Integer":
       rustType = "u64";
@@ -771,6 +773,7 @@ function generateHumanReadableType(rustType) {
     ContractStatus: "status",
     CurrencyCode: "currency",
     Country: "country",
+    Decimal: "number",
     u128: "number",
     u64: "number",
     u32: "number",
@@ -789,7 +792,7 @@ function generateHumanReadableType(rustType) {
 function mapTypeForInkStorage(rustType) {
   // Map complex types to simple types for ink! storage
   if (rustType.includes("MonetaryAmount")) {
-    return "u128"; // Represent monetary amounts as u128 (smallest currency unit)
+    return "Decimal"; // Monetary amounts keep their fractional part, e.g. pence
   }
   if (rustType.includes("Period") || rustType.includes("Duration")) {
     return "u64"; // Represent time periods as seconds (u64)
@@ -911,6 +914,8 @@ ${constructorInit}
         }
 
         #[ink(constructor)]
+        // A constructor, not \`Default::default\`
+        #[allow(clippy::should_implement_trait)]
         pub fn default() -> Self {
             ${
               templateModel
@@ -1280,6 +1285,7 @@ function isCopyType(rustType) {
     "usize",
     "f32",
     "f64",
+    "Decimal",
   ];
 
   // Check for basic copy types
@@ -1350,6 +1356,14 @@ function createInkCargoToml(
   outputPath,
   projectName = "concerto-smart-contract"
 ) {
+  // Shared no_std types such as Decimal live next to the deployed contracts
+  const commonPath = path
+    .relative(
+      outputPath,
+      path.join(__dirname, "..", "..", "inkathon", "contracts", "common")
+    )
+    .split(path.sep)
+    .join("/");
   const cargoToml = `[package]
 name = "${projectName}"
 version = "0.1.0"
@@ -1360,6 +1374,7 @@ edition = "2021"
 ink = { version = "5.1.1", default-features = false }
 scale = { package = "parity-scale-codec", version = "3", default-features = false, features = ["derive"] }
 scale-info = { version = "2.6", default-features = false, features = ["derive"], optional = true }
+contract-common = { path = "${commonPath}", default-features = false }
 
 [dev-dependencies]
 ink_e2e = { version = "5.1.1" }
@@ -1373,6 +1388,7 @@ std = [
     "ink/std",
     "scale/std",
     "scale-info/std",
+    "contract-common/std",
 ]
 ink-as-dependency = []
 e2e-tests = []
@@ -1408,15 +1424,23 @@ function createInkLibRs(
     contractTypes,
     contractName
   );
+  const usesDecimal = [
+    dataStructures,
+    enumStructures,
+    contractStorage,
+    contractEvents,
+    contractImpl,
+  ].some((code) => /\bDecimal\b/.test(code));
 
   const libRsContent = `#![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+// Public so the generated model types are part of the crate's API instead of dead code
 #[ink::contract]
-mod ${contractName.toLowerCase().replace(/[^a-z0-9_]/g, "_")} {
+pub mod ${contractName.toLowerCase().replace(/[^a-z0-9_]/g, "_")} {
     use ink::prelude::string::{String, ToString};
     use ink::prelude::vec::Vec;
     use ink::prelude::format;
-
+${usesDecimal ? "\n    pub use contract_common::Decimal;\n" : ""}
     // Error types
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -1467,6 +1491,7 @@ ${contractEvents}
 ${contractImpl}
 
     #[cfg(test)]
+    #[allow(clippy::bool_assert_comparison)]
     mod tests {
         use super::*;
 
@@ -2037,7 +2062,7 @@ function generateCollectionManagementMethods(contractTypes) {
       : fieldName;
 
     // Only generate utilities for complex types (not primitive types)
-    if (!["String", "u64", "u128", "bool"].includes(vecType)) {
+    if (!["String", "u64", "u128", "bool", "Decimal"].includes(vecType)) {
       methods += `
         // === ${fieldName.toUpperCase()} COLLECTION UTILITIES ===
 
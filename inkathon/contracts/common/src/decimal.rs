@@ -0,0 +1,202 @@
+/// Fixed-point decimal used for Concerto Double fields, carrying `Decimal::DECIMALS` digits
+#[derive(scale::Decode, scale::Encode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Decimal(u128);
+
+/// How to round results that have more fractional digits than can be stored
+#[derive(scale::Decode, scale::Encode, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Rounding {
+    #[default]
+    Down,
+    Up,
+    HalfUp,
+}
+
+impl Decimal {
+    /// Number of fractional digits carried by every value
+    pub const DECIMALS: u32 = 18;
+    /// Raw representation of `1`
+    pub const SCALE: u128 = 10u128.pow(Self::DECIMALS);
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(Self::SCALE);
+
+    /// Build from a raw value, i.e. `raw / 10^DECIMALS`
+    pub const fn from_raw(raw: u128) -> Self {
+        Self(raw)
+    }
+
+    /// Raw value, i.e. the decimal multiplied by `10^DECIMALS`
+    pub const fn raw(self) -> u128 {
+        self.0
+    }
+
+    /// Build from a whole number
+    pub fn from_integer(value: u128) -> Option<Self> {
+        value.checked_mul(Self::SCALE).map(Self)
+    }
+
+    /// Build from `mantissa / 10^decimals`, e.g. `from_parts(125, 1)` is `12.5`
+    pub fn from_parts(mantissa: u128, decimals: u32) -> Option<Self> {
+        let factor = 10u128.checked_pow(Self::DECIMALS.checked_sub(decimals)?)?;
+        mantissa.checked_mul(factor).map(Self)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Multiply by a whole number
+    pub fn checked_mul_int(self, value: u128) -> Option<Self> {
+        self.0.checked_mul(value).map(Self)
+    }
+
+    pub fn checked_mul(self, other: Self, rounding: Rounding) -> Option<Self> {
+        Self::mul_div(self.0, other.0, 1, rounding).map(Self)
+    }
+
+    pub fn checked_div(self, other: Self, rounding: Rounding) -> Option<Self> {
+        if other.0 == 0 {
+            return None;
+        }
+
+        // Long division one digit at a time so `self * SCALE` never has to fit in a u128
+        let mut quotient = (self.0 / other.0).checked_mul(Self::SCALE)?;
+        let mut remainder = self.0 % other.0;
+        let mut digit = Self::SCALE;
+        for _ in 0..Self::DECIMALS {
+            digit /= 10;
+            remainder = remainder.checked_mul(10)?;
+            quotient = quotient.checked_add((remainder / other.0) * digit)?;
+            remainder %= other.0;
+        }
+
+        Self::round(quotient, remainder, other.0, rounding).map(Self)
+    }
+
+    /// `percentage`% of this value, e.g. `12.5`% of `650000` is `81250`
+    pub fn percentage_of(self, percentage: Self, rounding: Rounding) -> Option<Self> {
+        Self::mul_div(self.0, percentage.0, 100, rounding).map(Self)
+    }
+
+    /// Whole part of the value after applying `rounding`
+    pub fn to_integer(self, rounding: Rounding) -> Option<u128> {
+        Self::round(self.0 / Self::SCALE, self.0 % Self::SCALE, Self::SCALE, rounding)
+    }
+
+    /// Round to `decimals` fractional digits, e.g. to pence with `decimals = 2`
+    pub fn round_to(self, decimals: u32, rounding: Rounding) -> Option<Self> {
+        let unit = 10u128.checked_pow(Self::DECIMALS.checked_sub(decimals)?)?;
+        let rounded = Self::round(self.0 / unit, self.0 % unit, unit, rounding)?;
+        rounded.checked_mul(unit).map(Self)
+    }
+
+    /// Compute `a * b / (divisor * SCALE)` on raw values without an intermediate overflow.
+    ///
+    /// Splitting `a = aq * SCALE + ar` and `b = bq * SCALE + br` gives
+    /// `a * b = (aq * b + ar * bq) * SCALE + ar * br`, where `ar * br < SCALE^2`.
+    fn mul_div(a: u128, b: u128, divisor: u128, rounding: Rounding) -> Option<u128> {
+        let (aq, ar) = (a / Self::SCALE, a % Self::SCALE);
+        let (bq, br) = (b / Self::SCALE, b % Self::SCALE);
+        let high = aq.checked_mul(b)?.checked_add(ar.checked_mul(bq)?)?;
+        let low = ar * br;
+
+        let full_divisor = divisor.checked_mul(Self::SCALE)?;
+        let carry = (high % divisor).checked_mul(Self::SCALE)?.checked_add(low)?;
+        let quotient = (high / divisor).checked_add(carry / full_divisor)?;
+        Self::round(quotient, carry % full_divisor, full_divisor, rounding)
+    }
+
+    /// Apply `rounding` to a quotient given its remainder and divisor
+    fn round(quotient: u128, remainder: u128, divisor: u128, rounding: Rounding) -> Option<u128> {
+        let round_up = match rounding {
+            Rounding::Down => false,
+            Rounding::Up => remainder > 0,
+            Rounding::HalfUp => remainder >= divisor - remainder,
+        };
+        if round_up {
+            quotient.checked_add(1)
+        } else {
+            Some(quotient)
+        }
+    }
+}
+
+impl core::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let integer = self.0 / Self::SCALE;
+        let mut fraction = self.0 % Self::SCALE;
+        if fraction == 0 {
+            return write!(f, "{}", integer);
+        }
+
+        let mut width = Self::DECIMALS as usize;
+        while fraction.is_multiple_of(10) {
+            fraction /= 10;
+            width -= 1;
+        }
+        write!(f, "{}.{:0width$}", integer, fraction, width = width)
+    }
+}
+
+impl core::fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whole(value: u128) -> Decimal {
+        Decimal::from_integer(value).unwrap()
+    }
+
+    #[test]
+    fn decimal_arithmetic_works() {
+        let price = Decimal::from_parts(6500005, 1).unwrap();
+        let rate = Decimal::from_parts(125, 1).unwrap();
+        assert_eq!(price.to_string(), "650000.5");
+        assert_eq!(
+            price.percentage_of(rate, Rounding::Down),
+            Decimal::from_parts(812_500_625, 4)
+        );
+        assert_eq!(
+            price.checked_mul(rate, Rounding::Down),
+            Decimal::from_parts(812_500_625, 2)
+        );
+        assert_eq!(whole(1).checked_div(whole(0), Rounding::Down), None);
+        assert_eq!(
+            whole(2).checked_div(whole(3), Rounding::Down).unwrap().raw(),
+            666_666_666_666_666_666
+        );
+        assert_eq!(
+            whole(2).checked_div(whole(3), Rounding::HalfUp).unwrap().raw(),
+            666_666_666_666_666_667
+        );
+        assert_eq!(Decimal::ZERO.checked_sub(Decimal::ONE), None);
+        assert_eq!(whole(1).checked_add(Decimal::ONE), Some(whole(2)));
+    }
+
+    #[test]
+    fn decimal_rounding_modes() {
+        let pence = Decimal::from_parts(1005, 3).unwrap();
+        assert_eq!(pence.round_to(2, Rounding::Down), Decimal::from_parts(100, 2));
+        assert_eq!(pence.round_to(2, Rounding::Up), Decimal::from_parts(101, 2));
+        assert_eq!(pence.round_to(2, Rounding::HalfUp), Decimal::from_parts(101, 2));
+        let below_half = Decimal::from_parts(1004, 3).unwrap();
+        assert_eq!(below_half.round_to(2, Rounding::HalfUp), Decimal::from_parts(100, 2));
+        assert_eq!(pence.round_to(19, Rounding::Down), None);
+    }
+}
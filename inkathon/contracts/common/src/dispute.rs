@@ -0,0 +1,141 @@
+use ink::prelude::vec::Vec;
+use ink::primitives::{AccountId, Hash};
+use ink::storage::traits::Packed;
+use ink::storage::Mapping;
+
+/// Upper bound on disputes open at once, which are checked by every frozen operation
+pub const MAX_OPEN_DISPUTES: u32 = 8;
+
+/// Milliseconds after a resolution before the same subject can be disputed again, so
+/// neither party can keep what it freezes frozen by reopening it
+pub const DISPUTE_COOLDOWN: u64 = 7 * 86_400_000;
+
+/// A disagreement put to the arbitrator, about a contract-specific `S` and decided with a
+/// contract-specific `O`
+#[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Dispute<S, O> {
+    pub dispute_id: u64,
+    pub subject: S,
+    pub opened_by: AccountId,
+    /// Hash of the evidence document held off-chain
+    pub evidence_hash: Hash,
+    pub opened_at: u64,
+    /// Set once the arbitrator resolves the dispute
+    pub outcome: Option<O>,
+    pub resolved_at: Option<u64>,
+}
+
+/// Dispute history and the disputes still open. Contracts check subjects, outcomes and
+/// callers, and apply outcomes; this keeps the bookkeeping the same across them.
+#[ink::storage_item]
+#[derive(Debug)]
+pub struct Disputes<S: Packed, O: Packed> {
+    disputes: Mapping<u64, Dispute<S, O>>,
+    dispute_count: u64,
+    open_disputes: Vec<u64>,
+    /// When each subject was last resolved
+    last_resolved: Mapping<S, u64>,
+}
+
+impl<S: Packed, O: Packed> Default for Disputes<S, O> {
+    fn default() -> Self {
+        Self {
+            disputes: Mapping::default(),
+            dispute_count: 0,
+            open_disputes: Vec::new(),
+            last_resolved: Mapping::default(),
+        }
+    }
+}
+
+impl<S: Packed + PartialEq + Clone, O: Packed + Clone> Disputes<S, O> {
+    /// Whether a new dispute about `subject` can be opened at `now`: none is open about it,
+    /// fewer than `MAX_OPEN_DISPUTES` are open, and its last resolution is past the cooldown
+    pub fn can_open(&self, subject: &S, now: u64) -> bool {
+        self.open_about(subject).is_none()
+            && (self.open_disputes.len() as u32) < MAX_OPEN_DISPUTES
+            && self
+                .last_resolved
+                .get(subject)
+                .is_none_or(|resolved_at| now >= resolved_at.saturating_add(DISPUTE_COOLDOWN))
+    }
+
+    /// Open a dispute, or `None` if `can_open` does not allow it. Returns the dispute.
+    pub fn open(
+        &mut self,
+        subject: S,
+        opened_by: AccountId,
+        evidence_hash: Hash,
+        now: u64,
+    ) -> Option<Dispute<S, O>> {
+        if !self.can_open(&subject, now) {
+            return None;
+        }
+        let dispute = Dispute {
+            dispute_id: self.dispute_count,
+            subject,
+            opened_by,
+            evidence_hash,
+            opened_at: now,
+            outcome: None,
+            resolved_at: None,
+        };
+        self.disputes.insert(dispute.dispute_id, &dispute);
+        self.dispute_count = dispute.dispute_id.saturating_add(1);
+        self.open_disputes.push(dispute.dispute_id);
+        Some(dispute)
+    }
+
+    /// Close an open dispute with `outcome`, or `None` if it is unknown or already resolved.
+    /// Returns the resolved dispute.
+    pub fn resolve(&mut self, dispute_id: u64, outcome: O, now: u64) -> Option<Dispute<S, O>> {
+        let mut dispute = self.disputes.get(dispute_id)?;
+        if dispute.outcome.is_some() {
+            return None;
+        }
+        self.open_disputes.retain(|open| *open != dispute_id);
+        dispute.outcome = Some(outcome);
+        dispute.resolved_at = Some(now);
+        self.disputes.insert(dispute_id, &dispute);
+        self.last_resolved.insert(&dispute.subject, &now);
+        Some(dispute)
+    }
+
+    pub fn get(&self, dispute_id: u64) -> Option<Dispute<S, O>> {
+        self.disputes.get(dispute_id)
+    }
+
+    /// Dispute history, oldest first
+    pub fn range(&self, start: u64, limit: u64) -> Vec<Dispute<S, O>> {
+        let end = start.saturating_add(limit).min(self.dispute_count);
+        (start..end)
+            .filter_map(|dispute_id| self.disputes.get(dispute_id))
+            .collect()
+    }
+
+    pub fn count(&self) -> u64 {
+        self.dispute_count
+    }
+
+    pub fn open_disputes(&self) -> Vec<Dispute<S, O>> {
+        self.open_disputes
+            .iter()
+            .filter_map(|dispute_id| self.disputes.get(dispute_id))
+            .collect()
+    }
+
+    pub fn open_about(&self, subject: &S) -> Option<Dispute<S, O>> {
+        self.open_disputes
+            .iter()
+            .filter_map(|dispute_id| self.disputes.get(dispute_id))
+            .find(|dispute| dispute.subject == *subject)
+    }
+
+    pub fn has_open(&self) -> bool {
+        !self.open_disputes.is_empty()
+    }
+}
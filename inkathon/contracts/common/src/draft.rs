@@ -0,0 +1,11 @@
+/// Document format the draft service renders a draft request to
+#[derive(scale::Decode, scale::Encode, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum DraftFormat {
+    #[default]
+    Markdown,
+    Pdf,
+}
@@ -0,0 +1,10 @@
+//! Types shared by the generated contracts, kept `no_std` so they build into contract Wasm
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod decimal;
+pub mod draft;
+pub mod dispute;
+
+pub use decimal::{Decimal, Rounding};
+pub use draft::DraftFormat;
+pub use dispute::{Dispute, Disputes, DISPUTE_COOLDOWN, MAX_OPEN_DISPUTES};
@@ -1,10 +1,23 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+// Types contracts that embed this one as a clause need, built with `ink-as-dependency`
+pub use self::latedeliveryandpenalty::{
+    ContractError, Decimal, Duration, LateDeliveryAndPenaltyRef, LateDeliveryAndPenaltyRequest,
+    PenaltyLedger, Rounding, TemporalUnit,
+};
+
+// Public so the generated model types are part of the crate's API instead of dead code
 #[ink::contract]
-mod latedeliveryandpenalty {
+// The generated constructor dispatch enum is sized by `new`'s arguments
+#[allow(clippy::large_enum_variant)]
+pub mod latedeliveryandpenalty {
+    use ink::prelude::format;
     use ink::prelude::string::{String, ToString};
     use ink::prelude::vec::Vec;
 
+    pub use contract_common::{Decimal, DraftFormat, Rounding, DISPUTE_COOLDOWN, MAX_OPEN_DISPUTES};
+    use contract_common::Disputes;
+
     // Error types
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -13,20 +26,50 @@ mod latedeliveryandpenalty {
         ContractPaused,
         InvalidInput,
         ProcessingFailed,
+        TransferFailed,
+        /// The clause has been terminated
+        ContractTerminated,
+        TerminationNotReached,
+        /// The operation is frozen while a dispute about it awaits the arbitrator
+        DisputeOpen,
     }
 
     pub type Result<T> = core::result::Result<T, ContractError>;
 
-    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    /// Upper bound on registered oracles, keeping attestation tallies cheap
+    pub const MAX_ORACLES: u32 = 16;
+
+    /// Upper bound on clauses hosted by one contract, keeping `get_clause_ids` bounded
+    pub const MAX_CLAUSES: u32 = 128;
+
+    /// Upper bound on stored holidays, keeping business day counts cheap
+    pub const MAX_HOLIDAYS: u32 = 256;
+
+    /// Upper bound on force majeure claims awaiting the buyer's response under one clause,
+    /// which are scanned on every penalty calculation
+    pub const MAX_PENDING_FORCE_MAJEURE_CLAIMS: u32 = 8;
+
+    /// Upper bound on audit log entries archived into one checkpoint
+    pub const MAX_AUDIT_ARCHIVE_BATCH: u64 = 256;
+
+    /// Concerto namespace and `$class` of the template this contract was generated from
+    pub const TEMPLATE_NAMESPACE: &str = "io.clause.latedeliveryandpenalty@0.1.0";
+    pub const TEMPLATE_CLASS: &str = "io.clause.latedeliveryandpenalty@0.1.0.LateDeliveryAndPenalty";
+    const DURATION_CLASS: &str = "org.accordproject.time@0.3.0.Duration";
+
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
+    /// A delivery claim. Its fields are kept for compatibility with the template model but
+    /// ignored; the contract's recorded delivery, accepted force majeure claims and the clause's
+    /// agreed goods value are used instead. Callers with nothing to record can pass the default.
     pub struct LateDeliveryAndPenaltyRequest {
         pub force_majeure: bool,
         pub agreed_delivery: u64,
         pub delivered_at: Option<u64>,
-        pub goods_value: u128,
+        pub goods_value: Decimal,
     }
 
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
@@ -35,10 +78,47 @@ mod latedeliveryandpenalty {
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     pub struct LateDeliveryAndPenaltyResponse {
-        pub penalty: u128,
+        pub penalty: Decimal,
         pub buyer_may_terminate: bool,
     }
 
+    /// How a penalty was derived from the clause terms. Delays are in milliseconds.
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PenaltyBreakdown {
+        pub delay: u64,
+        /// Delay after rounding fractional units of `fractional_part` up, or the number of
+        /// late business days expressed as whole days
+        pub rounded_delay: u64,
+        pub fractional_part: TemporalUnit,
+        pub lateness_counting: LatenessCounting,
+        pub periods: u128,
+        pub uncapped_penalty: Decimal,
+        pub cap: Decimal,
+        pub cap_hit: bool,
+        pub force_majeure_applied: bool,
+        /// Part of `delay` covered by accepted force majeure claims and not counted as late
+        pub force_majeure_excluded: u64,
+        /// Penalty awarded by the arbitrator, which replaces the calculated penalty
+        pub penalty_override: Option<Decimal>,
+    }
+
+    /// Result of evaluating a request without executing it
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PenaltyPreview {
+        pub response: LateDeliveryAndPenaltyResponse,
+        pub breakdown: PenaltyBreakdown,
+        /// Amount the bond would pay the buyer now, at most what it holds
+        pub payout: Balance,
+    }
+
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
     #[cfg_attr(
         feature = "std",
@@ -55,7 +135,22 @@ mod latedeliveryandpenalty {
     )]
     pub struct Duration {
         pub amount: u128,
-        pub unit: String,
+        pub unit: TemporalUnit,
+    }
+
+    impl Duration {
+        /// Length in milliseconds, or `None` if it does not fit in a timestamp
+        pub fn to_millis(&self) -> Option<u64> {
+            u64::try_from(self.amount)
+                .ok()?
+                .checked_mul(self.unit.to_millis())
+        }
+    }
+
+    impl core::fmt::Display for Duration {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "{} {}", self.amount, self.unit)
+        }
     }
 
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
@@ -65,7 +160,7 @@ mod latedeliveryandpenalty {
     )]
     pub struct Period {
         pub amount: u128,
-        pub unit: u64,
+        pub unit: PeriodUnit,
     }
 
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
@@ -89,7 +184,7 @@ mod latedeliveryandpenalty {
         December,
     }
 
-    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, PartialEq, Eq, Debug, Default)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
@@ -105,6 +200,185 @@ mod latedeliveryandpenalty {
         Sunday,
     }
 
+    /// Milliseconds in a UTC calendar day
+    pub const DAY_MILLIS: u64 = 86_400_000;
+
+    impl Day {
+        /// Day of the week for a count of days since 1970-01-01, which was a Thursday
+        pub const fn from_day_number(day_number: u64) -> Self {
+            match day_number % 7 {
+                0 => Day::Thursday,
+                1 => Day::Friday,
+                2 => Day::Saturday,
+                3 => Day::Sunday,
+                4 => Day::Monday,
+                5 => Day::Tuesday,
+                _ => Day::Wednesday,
+            }
+        }
+
+        /// Day of the week (UTC) for a Unix millisecond timestamp
+        pub const fn from_timestamp(timestamp: u64) -> Self {
+            Self::from_day_number(timestamp / DAY_MILLIS)
+        }
+    }
+
+    impl Month {
+        const ALL: [Month; 12] = [
+            Month::January,
+            Month::February,
+            Month::March,
+            Month::April,
+            Month::May,
+            Month::June,
+            Month::July,
+            Month::August,
+            Month::September,
+            Month::October,
+            Month::November,
+            Month::December,
+        ];
+
+        /// Month for `1..=12`
+        pub fn from_number(number: u32) -> Option<Self> {
+            let index = usize::try_from(number.checked_sub(1)?).ok()?;
+            Self::ALL.get(index).cloned()
+        }
+
+        /// Number of the month, January being `1`
+        pub fn number(&self) -> u32 {
+            self.clone() as u32 + 1
+        }
+
+        pub fn days_in(&self, year: u32) -> u32 {
+            match self {
+                Month::February if is_leap_year(year) => 29,
+                Month::February => 28,
+                Month::April | Month::June | Month::September | Month::November => 30,
+                _ => 31,
+            }
+        }
+    }
+
+    /// Quote and escape a string as a JSON string literal
+    fn json_string(value: &str) -> String {
+        let mut quoted = String::from("\"");
+        for c in value.chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    /// `0x` prefixed lowercase hex, used for hashes in audit log values
+    fn hex_string(bytes: &[u8]) -> String {
+        let mut hex = String::from("0x");
+        for byte in bytes {
+            hex.push_str(&format!("{:02x}", byte));
+        }
+        hex
+    }
+
+    fn is_leap_year(year: u32) -> bool {
+        year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+    }
+
+    /// A UTC calendar date from 1970-01-01 onwards
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct CalendarDate {
+        pub year: u32,
+        pub month: Month,
+        pub day: u32,
+    }
+
+    impl CalendarDate {
+        /// Date for a count of days since 1970-01-01.
+        ///
+        /// Uses the days-to-civil algorithm from <https://howardhinnant.github.io/date_algorithms.html>,
+        /// counting in 400 year eras that start on 1 March.
+        pub fn from_day_number(day_number: u64) -> Self {
+            let z = day_number + 719_468;
+            let era = z / 146_097;
+            let day_of_era = z % 146_097;
+            let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524
+                - day_of_era / 146_096)
+                / 365;
+            let day_of_year =
+                day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+            let shifted_month = (5 * day_of_year + 2) / 153;
+            let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+            let month = if shifted_month < 10 {
+                shifted_month + 3
+            } else {
+                shifted_month - 9
+            };
+            let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+            Self {
+                year: year as u32,
+                month: Month::from_number(month as u32).unwrap_or_default(),
+                day: day as u32,
+            }
+        }
+
+        pub fn from_timestamp(timestamp: u64) -> Self {
+            Self::from_day_number(timestamp / DAY_MILLIS)
+        }
+
+        /// Days since 1970-01-01, or `None` for an invalid or earlier date
+        pub fn to_day_number(&self) -> Option<u64> {
+            if self.year < 1970 || self.day == 0 || self.day > self.month.days_in(self.year) {
+                return None;
+            }
+
+            let month = u64::from(self.month.number());
+            let year = u64::from(self.year) - u64::from(month <= 2);
+            let era = year / 400;
+            let year_of_era = year % 400;
+            let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+            let day_of_year = (153 * shifted_month + 2) / 5 + u64::from(self.day) - 1;
+            let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+            Some(era * 146_097 + day_of_era - 719_468)
+        }
+
+        /// Unix milliseconds at the start of the day
+        pub fn to_timestamp(&self) -> Option<u64> {
+            self.to_day_number()?.checked_mul(DAY_MILLIS)
+        }
+    }
+
+    impl core::fmt::Display for CalendarDate {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "{:04}-{:02}-{:02}", self.year, self.month.number(), self.day)
+        }
+    }
+
+    /// How a clause measures lateness
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum LatenessCounting {
+        /// Elapsed time, rounded up to whole `fractional_part` units
+        #[default]
+        ElapsedTime,
+        /// Business days touched by the delay, skipping weekends and holidays
+        BusinessDays,
+    }
+
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
     #[cfg_attr(
         feature = "std",
@@ -119,6 +393,32 @@ mod latedeliveryandpenalty {
         Weeks,
     }
 
+    impl TemporalUnit {
+        /// Length of one unit in milliseconds
+        pub const fn to_millis(&self) -> u64 {
+            match self {
+                TemporalUnit::Seconds => 1_000,
+                TemporalUnit::Minutes => 60_000,
+                TemporalUnit::Hours => 3_600_000,
+                TemporalUnit::Days => 86_400_000,
+                TemporalUnit::Weeks => 604_800_000,
+            }
+        }
+    }
+
+    impl core::fmt::Display for TemporalUnit {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let unit = match self {
+                TemporalUnit::Seconds => "seconds",
+                TemporalUnit::Minutes => "minutes",
+                TemporalUnit::Hours => "hours",
+                TemporalUnit::Days => "days",
+                TemporalUnit::Weeks => "weeks",
+            };
+            f.write_str(unit)
+        }
+    }
+
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
     #[cfg_attr(
         feature = "std",
@@ -133,31 +433,432 @@ mod latedeliveryandpenalty {
         Years,
     }
 
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct FieldChange {
+        pub field_name: String,
+        pub old_value: String,
+        pub new_value: String,
+    }
 
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
-    pub struct AuditLogEntry {
-        pub caller: AccountId,
+    #[repr(u8)]
+    pub enum AuditLogEntry {
+        FunctionCall {
+            caller: AccountId,
+            timestamp: u64,
+            function_name: String,
+            request_id: u64,
+            clause_id: Option<String>,
+            field_changes: Vec<FieldChange>,
+        } = 0,
+        DirectFieldChange {
+            field_name: String,
+            changed_by: AccountId,
+            clause_id: Option<String>,
+            old_value: String,
+            new_value: String,
+            block_number: u64,
+            timestamp: u64,
+        } = 1,
+    }
+
+    impl AuditLogEntry {
+        /// Account that made the call or the change
+        pub fn caller(&self) -> AccountId {
+            match self {
+                AuditLogEntry::FunctionCall { caller, .. } => *caller,
+                AuditLogEntry::DirectFieldChange { changed_by, .. } => *changed_by,
+            }
+        }
+
+        pub fn timestamp(&self) -> u64 {
+            match self {
+                AuditLogEntry::FunctionCall { timestamp, .. }
+                | AuditLogEntry::DirectFieldChange { timestamp, .. } => *timestamp,
+            }
+        }
+    }
+
+    /// A page of audit log entries with their log indexes, and the cursor for the next page
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct AuditLogPage {
+        pub entries: Vec<(u64, AuditLogEntry)>,
+        pub next_cursor: Option<u64>,
+    }
+
+    /// Which audit log entries may be archived and pruned; by default nothing is
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct AuditRetention {
+        /// Entries older than this many milliseconds
+        pub max_age: Option<u64>,
+        /// Entries beyond this many of the most recent
+        pub max_entries: Option<u64>,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum DocumentHashAlgorithm {
+        #[default]
+        Blake2x256,
+        Sha2x256,
+    }
+
+    /// A rendered agreement document for a clause, identified by the hash of its text
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct DocumentRecord {
+        pub clause_id: String,
+        /// Starts at 1 for each clause
+        pub version: u32,
+        pub document_hash: Hash,
+        pub algorithm: DocumentHashAlgorithm,
+        /// Blake2x256 of the template data the document was rendered from
+        pub template_data_hash: Hash,
+        pub submitter: AccountId,
         pub timestamp: u64,
-        pub function_name: String,
+    }
+
+    /// Merkle root over archived entries `start..end`, kept after the entries are pruned
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct AuditCheckpoint {
+        pub start: u64,
+        pub end: u64,
+        pub root: Hash,
+        /// Hash chain value of the last archived entry
+        pub head: Hash,
+        pub archived_by: AccountId,
+        pub archived_at: u64,
+    }
+
+    /// Seller's performance bond, held in the contract's native token balance
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Bond {
+        pub amount: Balance,
+        pub penalty_paid: Balance,
+        pub released: bool,
+    }
+
+    /// Delivery facts recorded on-chain, in Unix milliseconds
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct DeliveryRecord {
+        pub agreed_delivery: u64,
+        pub dispatched_at: Option<u64>,
+        pub received_at: Option<u64>,
+        pub confirmed_by: Option<AccountId>,
+    }
+
+    /// Terms of one late delivery clause, stored by clause id
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ClauseTerms {
+        pub force_majeure: bool,
+        pub penalty_duration: Duration,
+        pub penalty_percentage: Decimal,
+        pub cap_percentage: Decimal,
+        pub termination: Duration,
+        pub fractional_part: TemporalUnit,
+        pub lateness_counting: LatenessCounting,
+        /// Value of the goods that penalties and the cap are a percentage of. Changing it
+        /// needs the agreement of both buyer and seller.
+        pub goods_value: Decimal,
+    }
+
+    /// A processed request and the response it produced, keyed by request id
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct RequestRecord {
+        pub request_id: u64,
+        pub clause_id: String,
+        pub submitter: AccountId,
+        pub timestamp: u64,
+        pub request: LateDeliveryAndPenaltyRequest,
+        pub response: LateDeliveryAndPenaltyResponse,
+    }
+
+    /// Running totals of a clause's penalty assessments, in the native token's smallest unit
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PenaltyLedger {
+        pub assessed: Balance,
+        pub acknowledged: Balance,
+        pub paid: Balance,
+        pub written_off: Balance,
+        /// Payments credited back to the seller, which the buyer owes them, after the
+        /// arbitrator awarded less than was paid
+        pub credited: Balance,
+        /// Taken off the assessed penalty by lower awards, written off or credited back
+        pub reduced: Balance,
+    }
+
+    impl PenaltyLedger {
+        pub fn outstanding(&self) -> Balance {
+            self.assessed
+                .saturating_sub(self.paid)
+                .saturating_sub(self.written_off)
+        }
+
+        /// The clause's penalty as assessed so far, after any lower award
+        pub fn net_assessed(&self) -> Balance {
+            self.assessed.saturating_sub(self.reduced)
+        }
+    }
+
+    /// An increase in a clause's penalty that the seller owes the buyer by `due_at`
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PenaltyAssessment {
+        pub assessment_id: u64,
+        pub clause_id: String,
+        /// Request whose evaluation, or dispute resolution, raised the penalty
         pub request_id: u64,
+        pub amount: Balance,
+        pub assessed_at: u64,
+        pub due_at: u64,
+        pub acknowledged_at: Option<u64>,
+        /// Paid from the bond or in instalments
+        pub paid: Balance,
+        pub written_off: Balance,
+        /// Part of `paid` credited back to the seller by a lower award
+        pub credited: Balance,
+    }
+
+    impl PenaltyAssessment {
+        pub fn outstanding(&self) -> Balance {
+            self.amount
+                .saturating_sub(self.paid)
+                .saturating_sub(self.written_off)
+        }
+
+        pub fn is_overdue(&self, now: u64) -> bool {
+            self.outstanding() > 0 && now > self.due_at
+        }
+    }
+
+    /// Outcome of the buyer exercising their right to terminate
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct TerminationRecord {
+        pub clause_id: String,
+        pub terminated_at: u64,
+        pub delay: u64,
+        pub penalty: Decimal,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum ForceMajeureCategory {
+        NaturalDisaster,
+        Epidemic,
+        War,
+        GovernmentAction,
+        LabourDispute,
+        InfrastructureFailure,
+        Other,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum ForceMajeureStatus {
+        /// Awaiting the buyer's response
+        Pending,
+        Accepted,
+        Disputed,
+        /// The buyer did not respond before the deadline
+        DeemedAccepted,
+    }
+
+    /// Seller's claim that a force majeure event prevented delivery between `start` and `end`
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ForceMajeureClaim {
+        pub claim_id: u64,
+        pub clause_id: String,
+        pub category: ForceMajeureCategory,
+        pub start: u64,
+        pub end: u64,
+        /// Hash of the evidence document held off-chain
+        pub evidence_hash: Hash,
+        pub filed_at: u64,
+        pub response_deadline: u64,
+        pub status: ForceMajeureStatus,
+        pub responded_at: Option<u64>,
+    }
+
+    impl ForceMajeureClaim {
+        /// Whether the claim window is excluded from lateness
+        pub fn is_accepted(&self) -> bool {
+            matches!(
+                self.status,
+                ForceMajeureStatus::Accepted | ForceMajeureStatus::DeemedAccepted
+            )
+        }
+    }
+
+    /// What the buyer and seller disagree about
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum DisputeSubject {
+        /// The penalty owed under a clause; freezes its claims and termination
+        Penalty { clause_id: String },
+        /// A force majeure claim the buyer disputed; freezes its clause's claims and termination
+        ForceMajeureClaim { claim_id: u64 },
+    }
+
+    /// The arbitrator's binding decision
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum DisputeOutcome {
+        /// Nothing changes
+        Dismissed,
+        /// The clause's penalty is fixed at `penalty`, and any part not yet paid is paid
+        /// from the bond
+        PenaltyOverride { penalty: Decimal },
+        /// The force majeure claim is accepted or stays disputed for good
+        ForceMajeureRuling { accepted: bool },
     }
 
+    pub type Dispute = contract_common::Dispute<DisputeSubject, DisputeOutcome>;
+
+    /// Start and end of a period of time, in milliseconds
+    type Window = (u64, u64);
+
     #[ink(storage)]
     pub struct LateDeliveryAndPenalty {
         owner: AccountId,
+        buyer: AccountId,
+        seller: AccountId,
+        arbitrator: AccountId,
+        attestor: Option<AccountId>,
+        /// Records penalties paid outside this contract, such as a supply agreement
+        settlement_agent: Option<AccountId>,
         paused: bool,
         audit_log: ink::storage::Mapping<u64, AuditLogEntry>,
         audit_log_count: u64,
-        force_majeure: bool,
-        penalty_duration: u64,
-        penalty_percentage: u128,
-        cap_percentage: u128,
-        termination: u64,
-        fractional_part: String,
+        /// Running hash of each audit log entry, chained from the previous entry's hash
+        audit_log_hashes: ink::storage::Mapping<u64, Hash>,
+        audit_log_head: Hash,
+        /// Log indexes of each caller's entries, by position in that caller's history
+        audit_log_by_caller: ink::storage::Mapping<(AccountId, u64), u64>,
+        audit_log_caller_count: ink::storage::Mapping<AccountId, u64>,
+        audit_log_caller_start: ink::storage::Mapping<AccountId, u64>,
+        /// Log indexes of function call entries, by position in that function's history
+        audit_log_by_function: ink::storage::Mapping<(String, u64), u64>,
+        audit_log_function_count: ink::storage::Mapping<String, u64>,
+        audit_log_function_start: ink::storage::Mapping<String, u64>,
+        /// First entry not yet archived and pruned
+        audit_log_start: u64,
+        /// Hash chain value of the last pruned entry
+        audit_archived_head: Hash,
+        audit_retention: AuditRetention,
+        audit_checkpoints: ink::storage::Mapping<u64, AuditCheckpoint>,
+        audit_checkpoint_count: u64,
+        pending_field_changes: Vec<FieldChange>,
+        requests: ink::storage::Mapping<u64, RequestRecord>,
+        request_count: u64,
+        documents: ink::storage::Mapping<Hash, DocumentRecord>,
+        /// Document hash of each version of a clause's agreement
+        document_versions: ink::storage::Mapping<(String, u32), Hash>,
+        document_counts: ink::storage::Mapping<String, u32>,
+        clauses: ink::storage::Mapping<String, ClauseTerms>,
+        clause_ids: Vec<String>,
+        weekend: Vec<Day>,
+        holidays: Vec<u64>,
+        /// Seller's performance bond for each clause
+        bonds: ink::storage::Mapping<String, Bond>,
+        penalty_ledgers: ink::storage::Mapping<String, PenaltyLedger>,
+        penalty_assessments: ink::storage::Mapping<u64, PenaltyAssessment>,
+        penalty_assessment_count: u64,
+        /// Assessment ids of each clause, by position in that clause's history
+        clause_penalty_assessments: ink::storage::Mapping<(String, u64), u64>,
+        clause_penalty_assessment_counts: ink::storage::Mapping<String, u64>,
+        /// Milliseconds the seller has to pay a new assessment
+        penalty_payment_period: u64,
+        /// Request id of each clause's latest claim, and of the one the seller acknowledged
+        last_claims: ink::storage::Mapping<String, u64>,
+        acknowledged_claims: ink::storage::Mapping<String, u64>,
+        deliveries: ink::storage::Mapping<String, DeliveryRecord>,
+        oracles: Vec<AccountId>,
+        oracle_threshold: u32,
+        /// Each oracle's pending attestation, by clause id and oracle
+        attestations: ink::storage::Mapping<(String, AccountId), u64>,
+        /// Milliseconds two attestations may differ by and still agree
+        attestation_tolerance: u64,
+        force_majeure_claims: ink::storage::Mapping<u64, ForceMajeureClaim>,
+        force_majeure_claim_count: u64,
+        /// Ids of each clause's claims still awaiting the buyer's response
+        pending_force_majeure_claims: ink::storage::Mapping<String, Vec<u64>>,
+        /// Each clause's accepted force majeure windows, sorted and merged where they overlap
+        force_majeure_windows: ink::storage::Mapping<String, Vec<Window>>,
+        /// Milliseconds the buyer has to accept or dispute a force majeure claim
+        force_majeure_response_period: u64,
+        disputes: Disputes<DisputeSubject, DisputeOutcome>,
+        /// Penalties awarded by the arbitrator, by clause id
+        penalty_overrides: ink::storage::Mapping<String, Decimal>,
+        /// Goods value each party has agreed to for a clause, by clause id and party
+        goods_value_agreements: ink::storage::Mapping<(String, AccountId), Decimal>,
+        /// Terminated clauses, which reject requests and updates while queries keep working
+        terminations: ink::storage::Mapping<String, TerminationRecord>,
     }
 
     #[ink(event)]
@@ -184,15 +885,31 @@ mod latedeliveryandpenalty {
         pub submitter: AccountId,
         #[ink(topic)]
         pub request_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
     }
 
     #[ink(event)]
     pub struct LateDeliveryAndPenaltyResponseGenerated {
         #[ink(topic)]
         pub request_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
         pub success: bool,
     }
 
+    /// Everything that went into a clause's penalty, so the decision can be rebuilt from
+    /// events alone
+    #[ink(event)]
+    pub struct PenaltyAssessed {
+        #[ink(topic)]
+        pub request_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub breakdown: PenaltyBreakdown,
+        pub penalty: Decimal,
+        pub buyer_may_terminate: bool,
+    }
 
     #[ink(event)]
     pub struct FunctionCalled {
@@ -201,7 +918,239 @@ mod latedeliveryandpenalty {
         #[ink(topic)]
         pub function_name: String,
         pub request_id: u64,
+        pub clause_id: Option<String>,
+        pub timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct ClaimAcknowledged {
+        #[ink(topic)]
+        pub seller: AccountId,
+        #[ink(topic)]
+        pub request_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
+    }
+
+    #[ink(event)]
+    pub struct BondDeposited {
+        #[ink(topic)]
+        pub seller: AccountId,
+        #[ink(topic)]
+        pub buyer: AccountId,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub amount: Balance,
+        pub total: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PenaltyPaid {
+        #[ink(topic)]
+        pub buyer: AccountId,
+        #[ink(topic)]
+        pub request_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub amount: Balance,
+        pub total_paid: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PenaltyAssessmentRecorded {
+        #[ink(topic)]
+        pub assessment_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub request_id: u64,
+        pub amount: Balance,
+        /// Part paid from the bond straight away
+        pub paid: Balance,
+        pub due_at: u64,
+    }
+
+    #[ink(event)]
+    pub struct PenaltyAssessmentAcknowledged {
+        #[ink(topic)]
+        pub assessment_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub seller: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct PenaltyInstalmentPaid {
+        #[ink(topic)]
+        pub assessment_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub amount: Balance,
+        pub outstanding: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PenaltyWrittenOff {
+        #[ink(topic)]
+        pub assessment_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub amount: Balance,
+        pub outstanding: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PenaltyCredited {
+        #[ink(topic)]
+        pub assessment_id: u64,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub amount: Balance,
+        pub credited: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BondReleased {
+        #[ink(topic)]
+        pub seller: AccountId,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DeliveryDispatched {
+        #[ink(topic)]
+        pub seller: AccountId,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct DeliveryReceived {
+        #[ink(topic)]
+        pub confirmed_by: AccountId,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub agreed_delivery: u64,
+        pub timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct ForceMajeureClaimFiled {
+        #[ink(topic)]
+        pub claim_id: u64,
+        #[ink(topic)]
+        pub seller: AccountId,
+        pub clause_id: String,
+        pub category: ForceMajeureCategory,
+        pub start: u64,
+        pub end: u64,
+        pub evidence_hash: Hash,
+        pub response_deadline: u64,
+    }
+
+    #[ink(event)]
+    pub struct ForceMajeureClaimResolved {
+        #[ink(topic)]
+        pub claim_id: u64,
+        pub status: ForceMajeureStatus,
+        pub timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct DisputeOpened {
+        #[ink(topic)]
+        pub dispute_id: u64,
+        #[ink(topic)]
+        pub opened_by: AccountId,
+        pub subject: DisputeSubject,
+        pub evidence_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        pub dispute_id: u64,
+        #[ink(topic)]
+        pub arbitrator: AccountId,
+        pub outcome: DisputeOutcome,
+    }
+
+    #[ink(event)]
+    pub struct ContractTerminated {
+        #[ink(topic)]
+        pub buyer: AccountId,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub delay: u64,
+        pub penalty: Decimal,
+    }
+
+    /// Asks the draft service to render the template. PropertySale emits the same fields, and
+    /// the service decodes them with the contract's ABI.
+    #[ink(event)]
+    pub struct DraftRequested {
+        #[ink(topic)]
+        pub requester: AccountId,
+        pub request_id: u64,
+        /// Contract data in Concerto JSON, as in `test-template-data.json`
+        pub template_data: String,
         pub timestamp: u64,
+        pub output_format: DraftFormat,
+        pub template_class: String,
+        pub namespace: String,
+    }
+
+    #[ink(event)]
+    pub struct DocumentRegistered {
+        #[ink(topic)]
+        pub document_hash: Hash,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub version: u32,
+        pub template_data_hash: Hash,
+        pub submitter: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ClauseAdded {
+        #[ink(topic)]
+        pub clause_id: String,
+        #[ink(topic)]
+        pub added_by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OracleAdded {
+        #[ink(topic)]
+        pub oracle: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OracleRemoved {
+        #[ink(topic)]
+        pub oracle: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct DeliveryAttested {
+        #[ink(topic)]
+        pub oracle: AccountId,
+        #[ink(topic)]
+        pub clause_id: String,
+        pub delivered_at: u64,
+        pub agreeing: u32,
+        pub threshold: u32,
+    }
+
+    #[ink(event)]
+    pub struct AuditLogArchived {
+        #[ink(topic)]
+        pub checkpoint_id: u64,
+        pub start: u64,
+        pub end: u64,
+        pub root: Hash,
     }
 
     #[ink(event)]
@@ -210,6 +1159,7 @@ mod latedeliveryandpenalty {
         pub field_name: String,
         #[ink(topic)]
         pub changed_by: AccountId,
+        pub clause_id: Option<String>,
         pub old_value: String,
         pub new_value: String,
         pub block_number: u64,
@@ -217,43 +1167,112 @@ mod latedeliveryandpenalty {
     }
 
     impl LateDeliveryAndPenalty {
+        /// Deploy with a first clause; more can be added with `add_clause`. Fails with
+        /// `InvalidInput` when the terms are invalid, such as a zero penalty duration
         #[ink(constructor)]
+        #[allow(clippy::too_many_arguments)]
         pub fn new(
+            buyer: AccountId,
+            seller: AccountId,
+            arbitrator: AccountId,
+            agreed_delivery: u64,
+            clause_id: String,
             force_majeure: bool,
-            penalty_duration: u64,
-            penalty_percentage: u128,
-            cap_percentage: u128,
-            termination: u64,
-            fractional_part: String,
-        ) -> Self {
-            let caller = Self::env().caller();
-            
-            Self::env().emit_event(ContractCreated { owner: caller });
-
-            Self {
-                owner: caller,
-                paused: false,
-                audit_log: ink::storage::Mapping::default(),
-                audit_log_count: 0,
+            penalty_duration: Duration,
+            penalty_percentage: Decimal,
+            cap_percentage: Decimal,
+            termination: Duration,
+            fractional_part: TemporalUnit,
+            goods_value: Decimal,
+        ) -> Result<Self> {
+            let terms = ClauseTerms {
                 force_majeure,
                 penalty_duration,
                 penalty_percentage,
                 cap_percentage,
                 termination,
                 fractional_part,
-            }
+                lateness_counting: LatenessCounting::default(),
+                goods_value,
+            };
+            Self::validate_terms(&terms)?;
+
+            let mut contract = Self::init(buyer, seller, arbitrator);
+            contract.insert_clause(clause_id, &terms, agreed_delivery);
+            Ok(contract)
         }
 
+        /// Deploy with the caller as every party and no clauses
         #[ink(constructor)]
+        // A constructor, not `Default::default`
+        #[allow(clippy::should_implement_trait)]
         pub fn default() -> Self {
-            Self::new(
-                false,
-                0,
-                0,
-                0,
-                0,
-                String::new(),
-            )
+            let caller = Self::env().caller();
+            Self::init(caller, caller, caller)
+        }
+
+        fn init(buyer: AccountId, seller: AccountId, arbitrator: AccountId) -> Self {
+            let caller = Self::env().caller();
+
+            Self::env().emit_event(ContractCreated { owner: caller });
+
+            Self {
+                owner: caller,
+                buyer,
+                seller,
+                arbitrator,
+                attestor: None,
+                settlement_agent: None,
+                paused: false,
+                audit_log: ink::storage::Mapping::default(),
+                audit_log_count: 0,
+                audit_log_hashes: ink::storage::Mapping::default(),
+                audit_log_head: Hash::default(),
+                audit_log_by_caller: ink::storage::Mapping::default(),
+                audit_log_caller_count: ink::storage::Mapping::default(),
+                audit_log_caller_start: ink::storage::Mapping::default(),
+                audit_log_by_function: ink::storage::Mapping::default(),
+                audit_log_function_count: ink::storage::Mapping::default(),
+                audit_log_function_start: ink::storage::Mapping::default(),
+                audit_log_start: 0,
+                audit_archived_head: Hash::default(),
+                audit_retention: AuditRetention::default(),
+                audit_checkpoints: ink::storage::Mapping::default(),
+                audit_checkpoint_count: 0,
+                pending_field_changes: Vec::new(),
+                requests: ink::storage::Mapping::default(),
+                request_count: 0,
+                documents: ink::storage::Mapping::default(),
+                document_versions: ink::storage::Mapping::default(),
+                document_counts: ink::storage::Mapping::default(),
+                clauses: ink::storage::Mapping::default(),
+                clause_ids: Vec::new(),
+                weekend: Vec::from([Day::Saturday, Day::Sunday]),
+                holidays: Vec::new(),
+                bonds: ink::storage::Mapping::default(),
+                penalty_ledgers: ink::storage::Mapping::default(),
+                penalty_assessments: ink::storage::Mapping::default(),
+                penalty_assessment_count: 0,
+                clause_penalty_assessments: ink::storage::Mapping::default(),
+                clause_penalty_assessment_counts: ink::storage::Mapping::default(),
+                penalty_payment_period: 30 * DAY_MILLIS,
+                last_claims: ink::storage::Mapping::default(),
+                acknowledged_claims: ink::storage::Mapping::default(),
+                deliveries: ink::storage::Mapping::default(),
+                oracles: Vec::new(),
+                oracle_threshold: 1,
+                attestations: ink::storage::Mapping::default(),
+                attestation_tolerance: 15 * 60 * 1000,
+                force_majeure_claims: ink::storage::Mapping::default(),
+                pending_force_majeure_claims: ink::storage::Mapping::default(),
+                force_majeure_windows: ink::storage::Mapping::default(),
+                force_majeure_claim_count: 0,
+                force_majeure_response_period: 7 * DAY_MILLIS,
+                disputes: Disputes::default(),
+                penalty_overrides: ink::storage::Mapping::default(),
+                goods_value_agreements: ink::storage::Mapping::default(),
+                terminations: ink::storage::Mapping::default(),
+            }
         }
 
         #[ink(message)]
@@ -261,6 +1280,21 @@ mod latedeliveryandpenalty {
             self.owner
         }
 
+        #[ink(message)]
+        pub fn get_buyer(&self) -> AccountId {
+            self.buyer
+        }
+
+        #[ink(message)]
+        pub fn get_seller(&self) -> AccountId {
+            self.seller
+        }
+
+        #[ink(message)]
+        pub fn get_arbitrator(&self) -> AccountId {
+            self.arbitrator
+        }
+
         #[ink(message)]
         pub fn is_paused(&self) -> bool {
             self.paused
@@ -272,7 +1306,7 @@ mod latedeliveryandpenalty {
             if caller != self.owner {
                 return Err(ContractError::Unauthorized);
             }
-            
+
             self.paused = true;
             self.env().emit_event(ContractPaused { by: caller });
             Ok(())
@@ -284,298 +1318,4820 @@ mod latedeliveryandpenalty {
             if caller != self.owner {
                 return Err(ContractError::Unauthorized);
             }
-            
+
             self.paused = false;
             self.env().emit_event(ContractUnpaused { by: caller });
             Ok(())
         }
 
+        /// Buyer claims a clause's penalty, evaluated at the current block against the
+        /// contract's recorded delivery, accepted force majeure claims and agreed goods value.
+        /// `request` is kept in the request history but none of its fields are used.
         #[ink(message)]
         pub fn late_delivery_and_penalty(
             &mut self,
-            _request: LateDeliveryAndPenaltyRequest,
+            clause_id: String,
+            request: LateDeliveryAndPenaltyRequest,
         ) -> Result<LateDeliveryAndPenaltyResponse> {
             if self.paused {
                 return Err(ContractError::ContractPaused);
             }
 
-            let request_id = self.env().block_number() as u64;
-            
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
+            // Only the buyer may submit delivery claims
+            if self.env().caller() != self.buyer {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let terms = self.clauses.get(&clause_id).ok_or(ContractError::InvalidInput)?;
+            if self.is_penalty_frozen(&clause_id) {
+                return Err(ContractError::DisputeOpen);
+            }
+
+            let request_id = self.next_request_id();
+
             self.env().emit_event(LateDeliveryAndPenaltyRequestSubmitted {
                 submitter: self.env().caller(),
                 request_id,
+                clause_id: clause_id.clone(),
             });
 
             // === BEGIN CUSTOM LOGIC ===
-            // TODO: Implement your late delivery and penalty logic here
-            let response = LateDeliveryAndPenaltyResponse {
-                penalty: 0,
-                buyer_may_terminate: false,
-            };
+            // Evaluate against the recorded receipt, or "now" if the goods have not arrived yet
+            let now = self.env().block_timestamp();
+            self.settle_force_majeure_claims(&clause_id, now);
+            let (response, breakdown) = self.calculate_penalty(&clause_id, &terms, now)?;
+            self.assess_penalty(&clause_id, response.penalty, request_id)?;
+            self.emit_penalty_assessed(request_id, &clause_id, &response, breakdown);
             // === END CUSTOM LOGIC ===
-            
+
             // Log function call for audit trail
-            self.log_function_call("late_delivery_and_penalty", request_id);
-            
+            self.store_request(request_id, &clause_id, &request, &response);
+            self.log_clause_call("late_delivery_and_penalty", &clause_id, request_id);
+            self.last_claims.insert(&clause_id, &request_id);
+
             self.env().emit_event(LateDeliveryAndPenaltyResponseGenerated {
                 request_id,
+                clause_id,
                 success: true,
             });
 
             Ok(response)
         }
 
+        /// Evaluate a claim as `late_delivery_and_penalty` would at the current block,
+        /// without paying, logging or emitting events. The request is ignored, as it is there.
         #[ink(message)]
-        pub fn get_force_majeure(&self) -> bool {
-            self.force_majeure
+        pub fn preview_penalty(
+            &self,
+            clause_id: String,
+            _request: LateDeliveryAndPenaltyRequest,
+        ) -> Result<PenaltyPreview> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
+            let terms = self.clauses.get(&clause_id).ok_or(ContractError::InvalidInput)?;
+            if self.is_penalty_frozen(&clause_id) {
+                return Err(ContractError::DisputeOpen);
+            }
+
+            let now = self.env().block_timestamp();
+            let (response, breakdown) = self.calculate_penalty(&clause_id, &terms, now)?;
+            let payout = self.penalty_payout(&clause_id, response.penalty)?;
+            Ok(PenaltyPreview {
+                response,
+                breakdown,
+                payout,
+            })
         }
 
+        /// Seller acknowledges the buyer's latest delivery claim under a clause
         #[ink(message)]
-        pub fn get_penalty_duration(&self) -> u64 {
-            self.penalty_duration
+        pub fn acknowledge_claim(&mut self, clause_id: String, request_id: u64) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.seller {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.last_claims.get(&clause_id) != Some(request_id) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            self.acknowledged_claims.insert(&clause_id, &request_id);
+            self.env().emit_event(ClaimAcknowledged {
+                seller: caller,
+                request_id,
+                clause_id: clause_id.clone(),
+            });
+            let log_request_id = self.next_request_id();
+            self.log_clause_call("acknowledge_claim", &clause_id, log_request_id);
+            Ok(())
         }
 
         #[ink(message)]
-        pub fn get_penalty_percentage(&self) -> u128 {
-            self.penalty_percentage
+        pub fn get_last_claim(&self, clause_id: String) -> Option<u64> {
+            self.last_claims.get(&clause_id)
         }
 
         #[ink(message)]
-        pub fn get_cap_percentage(&self) -> u128 {
-            self.cap_percentage
+        pub fn get_acknowledged_claim(&self, clause_id: String) -> Option<u64> {
+            self.acknowledged_claims.get(&clause_id)
+        }
+
+        // === REQUEST HISTORY ===
+
+        /// Allocate the next request id; ids are never reused, even within a block
+        fn next_request_id(&mut self) -> u64 {
+            let request_id = self.request_count;
+            self.request_count = self.request_count.saturating_add(1);
+            request_id
+        }
+
+        fn store_request(
+            &mut self,
+            request_id: u64,
+            clause_id: &str,
+            request: &LateDeliveryAndPenaltyRequest,
+            response: &LateDeliveryAndPenaltyResponse,
+        ) {
+            let record = RequestRecord {
+                request_id,
+                clause_id: clause_id.to_string(),
+                submitter: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+                request: request.clone(),
+                response: response.clone(),
+            };
+            self.requests.insert(request_id, &record);
         }
 
         #[ink(message)]
-        pub fn get_termination(&self) -> u64 {
-            self.termination
+        pub fn get_request_count(&self) -> u64 {
+            self.request_count
         }
 
         #[ink(message)]
-        pub fn get_fractional_part(&self) -> String {
-            self.fractional_part.clone()
+        pub fn get_request(&self, request_id: u64) -> Option<RequestRecord> {
+            self.requests.get(request_id)
         }
 
         #[ink(message)]
-        pub fn set_force_majeure(&mut self, new_value: bool) -> Result<()> {
-            if self.paused {
-                return Err(ContractError::ContractPaused);
-            }
-            
-            let caller = self.env().caller();
-            if caller != self.owner {
-                return Err(ContractError::Unauthorized);
-            }
-            
-            if self.force_majeure != new_value {
-                let old_value = self.force_majeure.to_string();
-                let new_value_str = new_value.to_string();
-                self.log_field_change("force_majeure", &old_value, &new_value_str);
-                self.force_majeure = new_value;
-            } else {
-                self.force_majeure = new_value;
+        pub fn get_requests(&self, start: u64, limit: u64) -> Vec<RequestRecord> {
+            let mut records = Vec::new();
+            let end = start.saturating_add(limit).min(self.request_count);
+
+            for i in start..end {
+                if let Some(record) = self.requests.get(i) {
+                    records.push(record);
+                }
             }
-            Ok(())
+
+            records
         }
 
+        // === TERMINATION ===
+
+        /// Buyer terminates a clause once its recorded delay reaches the clause's termination
+        /// period.
+        ///
+        /// The final penalty is paid from the clause's bond and the remainder returned to the
+        /// seller. Afterwards requests and delivery updates for the clause are rejected, while
+        /// queries and the other clauses keep working.
         #[ink(message)]
-        pub fn set_penalty_duration(&mut self, new_value: u64) -> Result<()> {
+        pub fn terminate(
+            &mut self,
+            clause_id: String,
+            request: LateDeliveryAndPenaltyRequest,
+        ) -> Result<LateDeliveryAndPenaltyResponse> {
             if self.paused {
                 return Err(ContractError::ContractPaused);
             }
-            
-            let caller = self.env().caller();
-            if caller != self.owner {
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
+            let buyer = self.env().caller();
+            if buyer != self.buyer {
                 return Err(ContractError::Unauthorized);
             }
-            
-            if self.penalty_duration != new_value {
-                let old_str = self.penalty_duration.to_string();
-                let new_str = new_value.to_string();
-                self.log_field_change("penalty_duration", &old_str, &new_str);
-                self.penalty_duration = new_value;
-            } else {
-                self.penalty_duration = new_value;
+
+            let terms = self.clauses.get(&clause_id).ok_or(ContractError::InvalidInput)?;
+            if self.is_penalty_frozen(&clause_id) {
+                return Err(ContractError::DisputeOpen);
             }
-            Ok(())
+
+            let now = self.env().block_timestamp();
+            self.settle_force_majeure_claims(&clause_id, now);
+            let (response, breakdown) = self.calculate_penalty(&clause_id, &terms, now)?;
+            if !response.buyer_may_terminate {
+                return Err(ContractError::TerminationNotReached);
+            }
+
+            let request_id = self.next_request_id();
+            self.assess_penalty(&clause_id, response.penalty, request_id)?;
+            self.emit_penalty_assessed(request_id, &clause_id, &response, breakdown);
+            if self.bonds.get(&clause_id).is_some_and(|bond| !bond.released) {
+                self.return_bond(&clause_id)?;
+            }
+
+            let delay = self.recorded_delay(&clause_id, now);
+            let record = TerminationRecord {
+                clause_id: clause_id.clone(),
+                terminated_at: now,
+                delay,
+                penalty: response.penalty,
+            };
+            self.terminations.insert(&clause_id, &record);
+            self.env().emit_event(ContractTerminated {
+                buyer,
+                clause_id: clause_id.clone(),
+                delay,
+                penalty: response.penalty,
+            });
+            self.store_request(request_id, &clause_id, &request, &response);
+            self.log_clause_call("terminate", &clause_id, request_id);
+
+            Ok(response)
+        }
+
+        #[ink(message)]
+        pub fn is_terminated(&self, clause_id: String) -> bool {
+            self.terminations.contains(&clause_id)
         }
 
         #[ink(message)]
-        pub fn set_penalty_percentage(&mut self, new_value: u128) -> Result<()> {
+        pub fn get_termination_record(&self, clause_id: String) -> Option<TerminationRecord> {
+            self.terminations.get(&clause_id)
+        }
+
+        // === PERFORMANCE BOND ===
+
+        /// Lock (or top up) the seller's performance bond for a clause in favour of the buyer.
+        ///
+        /// The clause's penalties are paid from the bond in the native token, reading goods
+        /// values in its smallest unit.
+        #[ink(message, payable)]
+        pub fn deposit_bond(&mut self, clause_id: String) -> Result<()> {
             if self.paused {
                 return Err(ContractError::ContractPaused);
             }
-            
-            let caller = self.env().caller();
-            if caller != self.owner {
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
+            let seller = self.env().caller();
+            if seller != self.seller {
                 return Err(ContractError::Unauthorized);
             }
-            
-            if self.penalty_percentage != new_value {
-                let old_str = self.penalty_percentage.to_string();
-                let new_str = new_value.to_string();
-                self.log_field_change("penalty_percentage", &old_str, &new_str);
-                self.penalty_percentage = new_value;
-            } else {
-                self.penalty_percentage = new_value;
+
+            let amount = self.env().transferred_value();
+            if amount == 0 || !self.clauses.contains(&clause_id) {
+                return Err(ContractError::InvalidInput);
             }
+
+            let mut bond = match self.bonds.get(&clause_id) {
+                Some(bond) => {
+                    if bond.released {
+                        return Err(ContractError::InvalidInput);
+                    }
+                    bond
+                }
+                None => Bond {
+                    amount: 0,
+                    penalty_paid: 0,
+                    released: false,
+                },
+            };
+            bond.amount = bond.amount.saturating_add(amount);
+
+            self.env().emit_event(BondDeposited {
+                seller,
+                buyer: self.buyer,
+                clause_id: clause_id.clone(),
+                amount,
+                total: bond.amount,
+            });
+            self.bonds.insert(&clause_id, &bond);
+
+            let request_id = self.next_request_id();
+            self.log_clause_call("deposit_bond", &clause_id, request_id);
             Ok(())
         }
 
+        /// Called by the buyer or seller once a clause's receipt is recorded to return the
+        /// remaining bond for it to the seller. The clause's final penalty is assessed first, so
+        /// the bond pays it whether or not the buyer has claimed.
         #[ink(message)]
-        pub fn set_cap_percentage(&mut self, new_value: u128) -> Result<()> {
+        pub fn release_bond(&mut self, clause_id: String) -> Result<Balance> {
             if self.paused {
                 return Err(ContractError::ContractPaused);
             }
-            
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
             let caller = self.env().caller();
-            if caller != self.owner {
+            if caller != self.buyer && caller != self.seller {
                 return Err(ContractError::Unauthorized);
             }
-            
-            if self.cap_percentage != new_value {
-                let old_str = self.cap_percentage.to_string();
-                let new_str = new_value.to_string();
-                self.log_field_change("cap_percentage", &old_str, &new_str);
-                self.cap_percentage = new_value;
-            } else {
-                self.cap_percentage = new_value;
+
+            let received = self
+                .deliveries
+                .get(&clause_id)
+                .is_some_and(|delivery| delivery.received_at.is_some());
+            if !received {
+                return Err(ContractError::InvalidInput);
+            }
+
+            // The bond must stay available for whatever penalty the arbitrator awards
+            if self.is_penalty_frozen(&clause_id) {
+                return Err(ContractError::DisputeOpen);
+            }
+
+            let request_id = self.next_request_id();
+            let now = self.env().block_timestamp();
+            self.settle_force_majeure_claims(&clause_id, now);
+            let terms = self.clauses.get(&clause_id).ok_or(ContractError::InvalidInput)?;
+            let (response, breakdown) = self.calculate_penalty(&clause_id, &terms, now)?;
+            self.assess_penalty(&clause_id, response.penalty, request_id)?;
+            self.emit_penalty_assessed(request_id, &clause_id, &response, breakdown);
+
+            let amount = self.return_bond(&clause_id)?;
+
+            self.log_clause_call("release_bond", &clause_id, request_id);
+            Ok(amount)
+        }
+
+        #[ink(message)]
+        pub fn get_bond(&self, clause_id: String) -> Option<Bond> {
+            self.bonds.get(&clause_id)
+        }
+
+        /// Penalty paid under a clause, from the bond or in instalments
+        #[ink(message)]
+        pub fn get_penalty_paid(&self, clause_id: String) -> Balance {
+            self.get_penalty_ledger(clause_id).paid
+        }
+
+        /// Transfer the remaining bond for a clause to the seller and mark it released
+        fn return_bond(&mut self, clause_id: &str) -> Result<Balance> {
+            let mut bond = self.bonds.get(clause_id).ok_or(ContractError::InvalidInput)?;
+            if bond.released {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let amount = bond.amount;
+            if amount > 0 {
+                self.env()
+                    .transfer(self.seller, amount)
+                    .map_err(|_| ContractError::TransferFailed)?;
             }
+            bond.amount = 0;
+            bond.released = true;
+
+            self.env().emit_event(BondReleased {
+                seller: self.seller,
+                clause_id: clause_id.to_string(),
+                amount,
+            });
+            self.bonds.insert(clause_id, &bond);
+            Ok(amount)
+        }
+
+        /// Record any increase of a clause's penalty over what has been assessed so far as a
+        /// new assessment, paying as much of it as the clause's bond covers when one is held.
+        /// The rest stays outstanding for the seller to pay in instalments.
+        fn assess_penalty(
+            &mut self,
+            clause_id: &str,
+            penalty: Decimal,
+            request_id: u64,
+        ) -> Result<()> {
+            let amount = self.penalty_increase(clause_id, penalty)?;
+            if amount == 0 {
+                return Ok(());
+            }
+
+            let paid = self.penalty_payout(clause_id, penalty)?;
+            if paid > 0 {
+                let mut bond = self.bonds.get(clause_id).ok_or(ContractError::ProcessingFailed)?;
+                self.env()
+                    .transfer(self.buyer, paid)
+                    .map_err(|_| ContractError::TransferFailed)?;
+                bond.amount = bond.amount.saturating_sub(paid);
+                bond.penalty_paid = bond.penalty_paid.saturating_add(paid);
+
+                self.env().emit_event(PenaltyPaid {
+                    buyer: self.buyer,
+                    request_id,
+                    clause_id: clause_id.to_string(),
+                    amount: paid,
+                    total_paid: bond.penalty_paid,
+                });
+                self.bonds.insert(clause_id, &bond);
+            }
+
+            let assessment_id = self.penalty_assessment_count;
+            let assessed_at = self.env().block_timestamp();
+            let due_at = assessed_at.saturating_add(self.penalty_payment_period);
+            let assessment = PenaltyAssessment {
+                assessment_id,
+                clause_id: clause_id.to_string(),
+                request_id,
+                amount,
+                assessed_at,
+                due_at,
+                acknowledged_at: None,
+                paid,
+                written_off: 0,
+                credited: 0,
+            };
+            self.penalty_assessments.insert(assessment_id, &assessment);
+            self.penalty_assessment_count = assessment_id.saturating_add(1);
+            let position = self.clause_penalty_assessment_counts.get(clause_id).unwrap_or(0);
+            self.clause_penalty_assessments
+                .insert((clause_id.to_string(), position), &assessment_id);
+            self.clause_penalty_assessment_counts
+                .insert(clause_id, &position.saturating_add(1));
+
+            let mut ledger = self.penalty_ledgers.get(clause_id).unwrap_or_default();
+            ledger.assessed = ledger.assessed.saturating_add(amount);
+            ledger.paid = ledger.paid.saturating_add(paid);
+            self.penalty_ledgers.insert(clause_id, &ledger);
+
+            self.env().emit_event(PenaltyAssessmentRecorded {
+                assessment_id,
+                clause_id: clause_id.to_string(),
+                request_id,
+                amount,
+                paid,
+                due_at,
+            });
+            Ok(())
+        }
+
+        fn emit_penalty_assessed(
+            &self,
+            request_id: u64,
+            clause_id: &str,
+            response: &LateDeliveryAndPenaltyResponse,
+            breakdown: PenaltyBreakdown,
+        ) {
+            self.env().emit_event(PenaltyAssessed {
+                request_id,
+                clause_id: clause_id.to_string(),
+                breakdown,
+                penalty: response.penalty,
+                buyer_may_terminate: response.buyer_may_terminate,
+            });
+        }
+
+        /// Part of a clause's `penalty` not yet assessed, in whole token units
+        fn penalty_increase(&self, clause_id: &str, penalty: Decimal) -> Result<Balance> {
+            let penalty = penalty
+                .to_integer(Rounding::Down)
+                .ok_or(ContractError::ProcessingFailed)?;
+            let assessed = self.penalty_ledgers.get(clause_id).unwrap_or_default().net_assessed();
+            Ok(penalty.saturating_sub(assessed))
+        }
+
+        /// Bring a clause's assessed penalty down to a lower `penalty` awarded by the
+        /// arbitrator. The difference is written off the newest outstanding assessments first,
+        /// and whatever was already paid beyond the award is credited back to the seller.
+        fn reduce_penalty(&mut self, clause_id: &str, penalty: Decimal) -> Result<()> {
+            let penalty = penalty
+                .to_integer(Rounding::Down)
+                .ok_or(ContractError::ProcessingFailed)?;
+            let mut ledger = self.penalty_ledgers.get(clause_id).unwrap_or_default();
+            let reduction = ledger.net_assessed().saturating_sub(penalty);
+            if reduction == 0 {
+                return Ok(());
+            }
+
+            let assessment_ids = self.clause_penalty_assessment_ids(clause_id);
+            let mut remaining = reduction;
+            for assessment_id in assessment_ids.iter().rev() {
+                let Some(mut assessment) = self.penalty_assessments.get(assessment_id) else {
+                    continue;
+                };
+                let amount = remaining.min(assessment.outstanding());
+                if amount == 0 {
+                    continue;
+                }
+                assessment.written_off = assessment.written_off.saturating_add(amount);
+                self.penalty_assessments.insert(assessment_id, &assessment);
+                ledger.written_off = ledger.written_off.saturating_add(amount);
+                remaining = remaining.saturating_sub(amount);
+                self.env().emit_event(PenaltyWrittenOff {
+                    assessment_id: *assessment_id,
+                    clause_id: clause_id.to_string(),
+                    amount,
+                    outstanding: assessment.outstanding(),
+                });
+            }
+            for assessment_id in assessment_ids.iter().rev() {
+                let Some(mut assessment) = self.penalty_assessments.get(assessment_id) else {
+                    continue;
+                };
+                let amount = remaining.min(assessment.paid.saturating_sub(assessment.credited));
+                if amount == 0 {
+                    continue;
+                }
+                assessment.credited = assessment.credited.saturating_add(amount);
+                self.penalty_assessments.insert(assessment_id, &assessment);
+                ledger.credited = ledger.credited.saturating_add(amount);
+                remaining = remaining.saturating_sub(amount);
+                self.env().emit_event(PenaltyCredited {
+                    assessment_id: *assessment_id,
+                    clause_id: clause_id.to_string(),
+                    amount,
+                    credited: assessment.credited,
+                });
+            }
+
+            // What the buyer already waived cannot be taken off again
+            ledger.reduced = ledger.reduced.saturating_add(reduction.saturating_sub(remaining));
+            self.penalty_ledgers.insert(clause_id, &ledger);
             Ok(())
         }
 
+        /// Ids of a clause's assessments, oldest first
+        fn clause_penalty_assessment_ids(&self, clause_id: &str) -> Vec<u64> {
+            let count = self.clause_penalty_assessment_counts.get(clause_id).unwrap_or(0);
+            (0..count)
+                .filter_map(|position| {
+                    self.clause_penalty_assessments
+                        .get((clause_id.to_string(), position))
+                })
+                .collect()
+        }
+
+        /// Part of a clause's `penalty` its unreleased bond would pay now, in whole token units,
+        /// which is no more than the bond holds
+        fn penalty_payout(&self, clause_id: &str, penalty: Decimal) -> Result<Balance> {
+            let bond = match self.bonds.get(clause_id) {
+                Some(bond) if !bond.released => bond,
+                _ => return Ok(0),
+            };
+
+            let amount = self.penalty_increase(clause_id, penalty)?;
+            Ok(amount.min(bond.amount))
+        }
+
+        // === PENALTY LEDGER ===
+        //
+        // Assessments without a bond to pay them are settled here. Debts outlive termination,
+        // so these messages keep working after it.
+
+        /// Seller acknowledges that an assessment is owed
         #[ink(message)]
-        pub fn set_termination(&mut self, new_value: u64) -> Result<()> {
+        pub fn acknowledge_penalty_assessment(&mut self, assessment_id: u64) -> Result<()> {
             if self.paused {
                 return Err(ContractError::ContractPaused);
             }
-            
-            let caller = self.env().caller();
-            if caller != self.owner {
+
+            let seller = self.env().caller();
+            if seller != self.seller {
                 return Err(ContractError::Unauthorized);
             }
-            
-            if self.termination != new_value {
-                let old_str = self.termination.to_string();
-                let new_str = new_value.to_string();
-                self.log_field_change("termination", &old_str, &new_str);
-                self.termination = new_value;
-            } else {
-                self.termination = new_value;
+
+            let mut assessment = self
+                .penalty_assessments
+                .get(assessment_id)
+                .ok_or(ContractError::InvalidInput)?;
+            if assessment.acknowledged_at.is_some() {
+                return Err(ContractError::InvalidInput);
+            }
+
+            assessment.acknowledged_at = Some(self.env().block_timestamp());
+            self.penalty_assessments.insert(assessment_id, &assessment);
+            let clause_id = assessment.clause_id;
+            let mut ledger = self.penalty_ledgers.get(&clause_id).unwrap_or_default();
+            ledger.acknowledged = ledger.acknowledged.saturating_add(assessment.amount);
+            self.penalty_ledgers.insert(&clause_id, &ledger);
+
+            self.env().emit_event(PenaltyAssessmentAcknowledged {
+                assessment_id,
+                clause_id: clause_id.clone(),
+                seller,
+            });
+            let request_id = self.next_request_id();
+            self.log_clause_call("acknowledge_penalty_assessment", &clause_id, request_id);
+            Ok(())
+        }
+
+        /// Seller pays all or part of an assessment's outstanding amount, which is passed on
+        /// to the buyer
+        #[ink(message, payable)]
+        pub fn pay_penalty_assessment(&mut self, assessment_id: u64) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.seller {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let amount = self.env().transferred_value();
+            let mut assessment = self
+                .penalty_assessments
+                .get(assessment_id)
+                .ok_or(ContractError::InvalidInput)?;
+            if amount == 0 || amount > assessment.outstanding() {
+                return Err(ContractError::InvalidInput);
             }
+
+            self.env()
+                .transfer(self.buyer, amount)
+                .map_err(|_| ContractError::TransferFailed)?;
+            assessment.paid = assessment.paid.saturating_add(amount);
+            self.penalty_assessments.insert(assessment_id, &assessment);
+            let clause_id = assessment.clause_id.clone();
+            let mut ledger = self.penalty_ledgers.get(&clause_id).unwrap_or_default();
+            ledger.paid = ledger.paid.saturating_add(amount);
+            self.penalty_ledgers.insert(&clause_id, &ledger);
+
+            self.env().emit_event(PenaltyInstalmentPaid {
+                assessment_id,
+                clause_id: clause_id.clone(),
+                amount,
+                outstanding: assessment.outstanding(),
+            });
+            let request_id = self.next_request_id();
+            self.log_clause_call("pay_penalty_assessment", &clause_id, request_id);
             Ok(())
         }
 
+        /// Buyer waives all or part of an assessment's outstanding amount
         #[ink(message)]
-        pub fn set_fractional_part(&mut self, new_value: String) -> Result<()> {
+        pub fn write_off_penalty_assessment(
+            &mut self,
+            assessment_id: u64,
+            amount: Balance,
+        ) -> Result<()> {
             if self.paused {
                 return Err(ContractError::ContractPaused);
             }
-            
-            let caller = self.env().caller();
-            if caller != self.owner {
+
+            if self.env().caller() != self.buyer {
                 return Err(ContractError::Unauthorized);
             }
-            
-            if self.fractional_part != new_value {
-                let old_value = self.fractional_part.clone();
-                self.log_field_change("fractional_part", &old_value, &new_value);
-                self.fractional_part = new_value;
-            } else {
-                self.fractional_part = new_value;
+
+            let mut assessment = self
+                .penalty_assessments
+                .get(assessment_id)
+                .ok_or(ContractError::InvalidInput)?;
+            if amount == 0 || amount > assessment.outstanding() {
+                return Err(ContractError::InvalidInput);
             }
+
+            assessment.written_off = assessment.written_off.saturating_add(amount);
+            self.penalty_assessments.insert(assessment_id, &assessment);
+            let clause_id = assessment.clause_id.clone();
+            let mut ledger = self.penalty_ledgers.get(&clause_id).unwrap_or_default();
+            ledger.written_off = ledger.written_off.saturating_add(amount);
+            self.penalty_ledgers.insert(&clause_id, &ledger);
+
+            self.env().emit_event(PenaltyWrittenOff {
+                assessment_id,
+                clause_id: clause_id.clone(),
+                amount,
+                outstanding: assessment.outstanding(),
+            });
+            let request_id = self.next_request_id();
+            self.log_clause_call("write_off_penalty_assessment", &clause_id, request_id);
             Ok(())
         }
 
+        /// The settlement agent records that `amount` of a clause's outstanding penalty was paid
+        /// to the buyer outside this contract, such as withheld from the price by a supply
+        /// agreement. It settles the oldest outstanding assessments first.
+        #[ink(message)]
+        pub fn record_penalty_settlement(
+            &mut self,
+            clause_id: String,
+            amount: Balance,
+        ) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
 
+            if Some(self.env().caller()) != self.settlement_agent {
+                return Err(ContractError::Unauthorized);
+            }
 
+            let mut ledger = self.penalty_ledgers.get(&clause_id).unwrap_or_default();
+            if amount == 0 || amount > ledger.outstanding() {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let mut remaining = amount;
+            for assessment_id in self.clause_penalty_assessment_ids(&clause_id) {
+                if remaining == 0 {
+                    break;
+                }
+                let Some(mut assessment) = self.penalty_assessments.get(assessment_id) else {
+                    continue;
+                };
+                if assessment.outstanding() == 0 {
+                    continue;
+                }
+                let settled = remaining.min(assessment.outstanding());
+                assessment.paid = assessment.paid.saturating_add(settled);
+                self.penalty_assessments.insert(assessment_id, &assessment);
+                remaining = remaining.saturating_sub(settled);
+                self.env().emit_event(PenaltyInstalmentPaid {
+                    assessment_id,
+                    clause_id: clause_id.clone(),
+                    amount: settled,
+                    outstanding: assessment.outstanding(),
+                });
+            }
+            ledger.paid = ledger.paid.saturating_add(amount);
+            self.penalty_ledgers.insert(&clause_id, &ledger);
+
+            let request_id = self.next_request_id();
+            self.log_clause_call("record_penalty_settlement", &clause_id, request_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_penalty_assessment(&self, assessment_id: u64) -> Option<PenaltyAssessment> {
+            self.penalty_assessments.get(assessment_id)
+        }
+
+        #[ink(message)]
+        pub fn get_penalty_assessments(&self, start: u64, limit: u64) -> Vec<PenaltyAssessment> {
+            let end = start.saturating_add(limit).min(self.penalty_assessment_count);
+            (start..end)
+                .filter_map(|assessment_id| self.penalty_assessments.get(assessment_id))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_penalty_assessment_count(&self) -> u64 {
+            self.penalty_assessment_count
+        }
+
+        /// Overdue assessments among ids `start..start + limit`
+        #[ink(message)]
+        pub fn get_overdue_penalty_assessments(
+            &self,
+            start: u64,
+            limit: u64,
+        ) -> Vec<PenaltyAssessment> {
+            let now = self.env().block_timestamp();
+            self.get_penalty_assessments(start, limit)
+                .into_iter()
+                .filter(|assessment| assessment.is_overdue(now))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn is_penalty_assessment_overdue(&self, assessment_id: u64) -> bool {
+            let now = self.env().block_timestamp();
+            self.penalty_assessments
+                .get(assessment_id)
+                .is_some_and(|assessment| assessment.is_overdue(now))
+        }
+
+        #[ink(message)]
+        pub fn get_penalty_ledger(&self, clause_id: String) -> PenaltyLedger {
+            self.penalty_ledgers.get(&clause_id).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_penalty_payment_period(&self) -> u64 {
+            self.penalty_payment_period
+        }
+
+        /// Milliseconds the seller has to pay assessments made from now on
+        #[ink(message)]
+        pub fn set_penalty_payment_period(&mut self, new_value: u64) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.penalty_payment_period != new_value {
+                let old_str = self.penalty_payment_period.to_string();
+                let new_str = new_value.to_string();
+                self.log_field_change("penalty_payment_period", &old_str, &new_str);
+                self.penalty_payment_period = new_value;
+            }
+            Ok(())
+        }
+
+        // === DELIVERY ===
+
+        /// Seller, or the designated attestor, records that the goods for a clause have been
+        /// dispatched
+        #[ink(message)]
+        pub fn record_dispatch(&mut self, clause_id: String) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
 
-        // === AUDIT LOG FUNCTIONALITY ===
-        
-        /// Record a function call in the audit log
-        fn log_function_call(&mut self, function_name: &str, request_id: u64) {
             let caller = self.env().caller();
+            if caller != self.seller && Some(caller) != self.attestor {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let mut delivery = self.deliveries.get(&clause_id).ok_or(ContractError::InvalidInput)?;
+            if delivery.dispatched_at.is_some() || delivery.received_at.is_some() {
+                return Err(ContractError::InvalidInput);
+            }
+
             let timestamp = self.env().block_timestamp();
-            
-            let log_entry = AuditLogEntry {
-                caller,
-                timestamp,
-                function_name: function_name.to_string(),
-                request_id,
-            };
-            
-            // Store with current count as index, then increment
-            self.audit_log.insert(self.audit_log_count, &log_entry);
-            self.audit_log_count = self.audit_log_count.saturating_add(1);
-            
-            self.env().emit_event(FunctionCalled {
-                caller,
-                function_name: function_name.to_string(),
-                request_id,
+            delivery.dispatched_at = Some(timestamp);
+            self.deliveries.insert(&clause_id, &delivery);
+            self.env().emit_event(DeliveryDispatched {
+                seller: self.seller,
+                clause_id: clause_id.clone(),
                 timestamp,
             });
+
+            let request_id = self.next_request_id();
+            self.log_clause_call("record_dispatch", &clause_id, request_id);
+            Ok(())
         }
 
-        /// Record a field change with before/after values
-        fn log_field_change(&mut self, field_name: &str, old_value: &str, new_value: &str) {
+        /// Buyer, or the designated attestor, confirms receipt of a clause's goods at the
+        /// current block time
+        #[ink(message)]
+        pub fn confirm_receipt(&mut self, clause_id: String) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
             let caller = self.env().caller();
+            if caller != self.buyer && Some(caller) != self.attestor {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let mut delivery = self.deliveries.get(&clause_id).ok_or(ContractError::InvalidInput)?;
+            if delivery.received_at.is_some() {
+                return Err(ContractError::InvalidInput);
+            }
+
             let timestamp = self.env().block_timestamp();
-            let block_number = self.env().block_number() as u64;
-            
-            self.env().emit_event(ContractDataChanged {
-                field_name: field_name.to_string(),
-                changed_by: caller,
-                old_value: old_value.to_string(),
-                new_value: new_value.to_string(),
-                block_number,
+            delivery.received_at = Some(timestamp);
+            delivery.confirmed_by = Some(caller);
+            self.deliveries.insert(&clause_id, &delivery);
+            self.env().emit_event(DeliveryReceived {
+                confirmed_by: caller,
+                clause_id: clause_id.clone(),
+                agreed_delivery: delivery.agreed_delivery,
                 timestamp,
             });
+
+            let request_id = self.next_request_id();
+            self.log_clause_call("confirm_receipt", &clause_id, request_id);
+            Ok(())
         }
 
+        #[ink(message)]
+        pub fn get_delivery(&self, clause_id: String) -> Option<DeliveryRecord> {
+            self.deliveries.get(&clause_id)
+        }
 
+        #[ink(message)]
+        pub fn get_agreed_delivery(&self, clause_id: String) -> Option<u64> {
+            self.deliveries.get(&clause_id).map(|delivery| delivery.agreed_delivery)
+        }
 
         #[ink(message)]
-        pub fn get_audit_log_count(&self) -> u64 {
-            self.audit_log_count
+        pub fn get_attestor(&self) -> Option<AccountId> {
+            self.attestor
         }
 
+        /// Designate (or clear) a third party who may record dispatch for the seller and
+        /// confirm receipt for the buyer
         #[ink(message)]
-        pub fn get_audit_log(&self, start: u64, limit: u64) -> Vec<AuditLogEntry> {
-            let mut entries = Vec::new();
-            let end = start.saturating_add(limit).min(self.audit_log_count);
-            
-            for i in start..end {
-                if let Some(entry) = self.audit_log.get(i) {
-                    entries.push(entry);
-                }
+        pub fn set_attestor(&mut self, new_value: Option<AccountId>) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
             }
-            
-            entries
-        }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
 
-        #[ink::test]
-        fn default_works() {
-            let contract = LateDeliveryAndPenalty::default();
-            assert_eq!(contract.is_paused(), false);
+            if self.attestor != new_value {
+                let old_str = format!("{:?}", self.attestor);
+                let new_str = format!("{:?}", new_value);
+                self.log_field_change("attestor", &old_str, &new_str);
+                self.attestor = new_value;
+            }
+            Ok(())
         }
 
-        #[ink::test]
-        fn pause_works() {
-            let mut contract = LateDeliveryAndPenalty::default();
-            assert_eq!(contract.pause(), Ok(()));
-            assert_eq!(contract.is_paused(), true);
+        #[ink(message)]
+        pub fn get_settlement_agent(&self) -> Option<AccountId> {
+            self.settlement_agent
         }
 
-        #[ink::test]
-        fn unpause_works() {
-            let mut contract = LateDeliveryAndPenalty::default();
-            assert_eq!(contract.pause(), Ok(()));
-            assert_eq!(contract.unpause(), Ok(()));
-            assert_eq!(contract.is_paused(), false);
+        /// Designate (or clear) the party who may record penalties paid outside this contract,
+        /// which clears them from the ledger
+        #[ink(message)]
+        pub fn set_settlement_agent(&mut self, new_value: Option<AccountId>) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.settlement_agent != new_value {
+                let old_str = format!("{:?}", self.settlement_agent);
+                let new_str = format!("{:?}", new_value);
+                self.log_field_change("settlement_agent", &old_str, &new_str);
+                self.settlement_agent = new_value;
+            }
+            Ok(())
+        }
+
+        // === DELIVERY ORACLES ===
+
+        /// Register a logistics oracle whose attestations can establish the delivery time
+        #[ink(message)]
+        pub fn add_oracle(&mut self, oracle: AccountId) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.oracles.contains(&oracle) || self.oracles.len() as u32 >= MAX_ORACLES {
+                return Err(ContractError::InvalidInput);
+            }
+
+            self.oracles.push(oracle);
+            self.env().emit_event(OracleAdded { oracle });
+
+            let request_id = self.next_request_id();
+            self.log_function_call("add_oracle", request_id);
+            Ok(())
+        }
+
+        /// Deregister an oracle and discard its pending attestation.
+        ///
+        /// Fails if the remaining oracles could no longer reach the threshold, so the last
+        /// oracle can only be replaced; lower the threshold first.
+        #[ink(message)]
+        pub fn remove_oracle(&mut self, oracle: AccountId) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let index = self
+                .oracles
+                .iter()
+                .position(|registered| *registered == oracle)
+                .ok_or(ContractError::InvalidInput)?;
+            let remaining = self.oracles.len() as u32 - 1;
+            if remaining < self.oracle_threshold {
+                return Err(ContractError::InvalidInput);
+            }
+
+            self.oracles.swap_remove(index);
+            for clause_id in self.clause_ids.clone() {
+                self.attestations.remove((clause_id, oracle));
+            }
+            self.env().emit_event(OracleRemoved { oracle });
+
+            let request_id = self.next_request_id();
+            self.log_function_call("remove_oracle", request_id);
+            Ok(())
+        }
+
+        /// Number of agreeing oracles (m of n) needed to establish the delivery time
+        #[ink(message)]
+        pub fn set_oracle_threshold(&mut self, new_value: u32) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if new_value == 0 || new_value > self.oracles.len() as u32 {
+                return Err(ContractError::InvalidInput);
+            }
+
+            if self.oracle_threshold != new_value {
+                let old_str = self.oracle_threshold.to_string();
+                let new_str = new_value.to_string();
+                self.log_field_change("oracle_threshold", &old_str, &new_str);
+                self.oracle_threshold = new_value;
+            }
+            Ok(())
+        }
+
+        /// Milliseconds two oracles' attestations may differ by and still agree
+        #[ink(message)]
+        pub fn set_attestation_tolerance(&mut self, new_value: u64) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if new_value > DAY_MILLIS {
+                return Err(ContractError::InvalidInput);
+            }
+
+            if self.attestation_tolerance != new_value {
+                let old_str = self.attestation_tolerance.to_string();
+                let new_str = new_value.to_string();
+                self.log_field_change("attestation_tolerance", &old_str, &new_str);
+                self.attestation_tolerance = new_value;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_attestation_tolerance(&self) -> u64 {
+            self.attestation_tolerance
+        }
+
+        /// An oracle attests the time (Unix milliseconds) a clause's goods were delivered.
+        ///
+        /// Oracles may revise their attestation until receipt is recorded. Attestations within
+        /// `attestation_tolerance` of this one agree with it; once `oracle_threshold` oracles
+        /// agree, the median of their times becomes the recorded receipt.
+        #[ink(message)]
+        pub fn submit_attestation(&mut self, clause_id: String, delivered_at: u64) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
+            let oracle = self.env().caller();
+            if !self.oracles.contains(&oracle) {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let mut delivery = self.deliveries.get(&clause_id).ok_or(ContractError::InvalidInput)?;
+            if delivery.received_at.is_some() || delivered_at > self.env().block_timestamp() {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let old_str = self
+                .attestations
+                .get((clause_id.clone(), oracle))
+                .map(|attested| attested.to_string())
+                .unwrap_or_default();
+            self.pending_field_changes.push(FieldChange {
+                field_name: "attestation".to_string(),
+                old_value: old_str,
+                new_value: delivered_at.to_string(),
+            });
+            self.attestations.insert((clause_id.clone(), oracle), &delivered_at);
+
+            let mut agreeing_times: Vec<u64> = self
+                .oracles
+                .iter()
+                .filter_map(|registered| self.attestations.get((clause_id.clone(), *registered)))
+                .filter(|attested| attested.abs_diff(delivered_at) <= self.attestation_tolerance)
+                .collect();
+            agreeing_times.sort_unstable();
+            let agreeing = agreeing_times.len() as u32;
+
+            self.env().emit_event(DeliveryAttested {
+                oracle,
+                clause_id: clause_id.clone(),
+                delivered_at,
+                agreeing,
+                threshold: self.oracle_threshold,
+            });
+            let request_id = self.next_request_id();
+            self.log_clause_call("submit_attestation", &clause_id, request_id);
+
+            if agreeing >= self.oracle_threshold {
+                let received_at = agreeing_times[(agreeing_times.len() - 1) / 2];
+                delivery.received_at = Some(received_at);
+                delivery.confirmed_by = Some(oracle);
+                self.deliveries.insert(&clause_id, &delivery);
+                self.env().emit_event(DeliveryReceived {
+                    confirmed_by: oracle,
+                    clause_id,
+                    agreed_delivery: delivery.agreed_delivery,
+                    timestamp: received_at,
+                });
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_oracles(&self) -> Vec<AccountId> {
+            self.oracles.clone()
+        }
+
+        #[ink(message)]
+        pub fn get_oracle_threshold(&self) -> u32 {
+            self.oracle_threshold
+        }
+
+        #[ink(message)]
+        pub fn get_attestation(&self, clause_id: String, oracle: AccountId) -> Option<u64> {
+            self.attestations.get((clause_id, oracle))
+        }
+
+        // === BUSINESS CALENDAR ===
+
+        /// Days of the week that are not business days
+        #[ink(message)]
+        pub fn set_weekend(&mut self, new_value: Vec<Day>) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            // At least one business day must remain, and each day may appear only once
+            let duplicated = new_value
+                .iter()
+                .enumerate()
+                .any(|(index, day)| new_value[..index].contains(day));
+            if duplicated || new_value.len() >= 7 {
+                return Err(ContractError::InvalidInput);
+            }
+
+            if self.weekend != new_value {
+                let old_str = format!("{:?}", self.weekend);
+                let new_str = format!("{:?}", new_value);
+                self.log_field_change("weekend", &old_str, &new_str);
+                self.weekend = new_value;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_weekend(&self) -> Vec<Day> {
+            self.weekend.clone()
+        }
+
+        /// Add a public holiday, which is not counted as a business day
+        #[ink(message)]
+        pub fn add_holiday(&mut self, date: CalendarDate) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let day_number = date.to_day_number().ok_or(ContractError::InvalidInput)?;
+            let index = match self.holidays.binary_search(&day_number) {
+                Ok(_) => return Err(ContractError::InvalidInput),
+                Err(index) => index,
+            };
+            if self.holidays.len() as u32 >= MAX_HOLIDAYS {
+                return Err(ContractError::InvalidInput);
+            }
+
+            self.holidays.insert(index, day_number);
+            self.log_field_change("holidays", "", &date.to_string());
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn remove_holiday(&mut self, date: CalendarDate) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let day_number = date.to_day_number().ok_or(ContractError::InvalidInput)?;
+            let index = self
+                .holidays
+                .binary_search(&day_number)
+                .map_err(|_| ContractError::InvalidInput)?;
+
+            self.holidays.remove(index);
+            self.log_field_change("holidays", &date.to_string(), "");
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_holidays(&self) -> Vec<CalendarDate> {
+            self.holidays
+                .iter()
+                .map(|day_number| CalendarDate::from_day_number(*day_number))
+                .collect()
+        }
+
+        /// Business days touched by the lateness window `(agreed, delivered]`.
+        ///
+        /// As with elapsed time, a partly late day counts as a whole day, so the agreed
+        /// delivery day itself counts when anything is delivered after the agreed time.
+        fn business_days_late(&self, agreed: u64, delivered: u64) -> u64 {
+            if delivered <= agreed {
+                return 0;
+            }
+
+            let first = agreed / DAY_MILLIS;
+            let last = (delivered - 1) / DAY_MILLIS;
+            let days = last - first + 1;
+
+            // Whole weeks contribute the same number of business days before holidays
+            let full_weeks = days / 7;
+            let weekdays_per_week = 7 - self.weekend.len() as u64;
+            let mut count = full_weeks * weekdays_per_week;
+            for day in first + full_weeks * 7..=last {
+                if !self.weekend.contains(&Day::from_day_number(day)) {
+                    count += 1;
+                }
+            }
+
+            let holidays = self
+                .holidays
+                .iter()
+                .filter(|day| (first..=last).contains(*day))
+                .filter(|day| !self.weekend.contains(&Day::from_day_number(**day)))
+                .count() as u64;
+            count - holidays
+        }
+
+        // === FORCE MAJEURE ===
+
+        /// Seller claims that a force majeure event prevented delivery under a clause during
+        /// `start..end`.
+        ///
+        /// The buyer has the response period to accept or dispute the claim; an unanswered
+        /// claim is deemed accepted. Accepted windows are excluded from the clause's lateness
+        /// if it has a force majeure provision. Returns the claim id.
+        #[ink(message)]
+        pub fn file_force_majeure_claim(
+            &mut self,
+            clause_id: String,
+            category: ForceMajeureCategory,
+            start: u64,
+            end: u64,
+            evidence_hash: Hash,
+        ) -> Result<u64> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let seller = self.env().caller();
+            if seller != self.seller {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
+            let mut pending = self.pending_force_majeure_claims.get(&clause_id).unwrap_or_default();
+            if !self.clauses.contains(&clause_id)
+                || start >= end
+                || evidence_hash == Hash::default()
+                || pending.len() as u32 >= MAX_PENDING_FORCE_MAJEURE_CLAIMS
+            {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let claim_id = self.force_majeure_claim_count;
+            let filed_at = self.env().block_timestamp();
+            let response_deadline = filed_at.saturating_add(self.force_majeure_response_period);
+            let claim = ForceMajeureClaim {
+                claim_id,
+                clause_id: clause_id.clone(),
+                category: category.clone(),
+                start,
+                end,
+                evidence_hash,
+                filed_at,
+                response_deadline,
+                status: ForceMajeureStatus::Pending,
+                responded_at: None,
+            };
+            self.force_majeure_claims.insert(claim_id, &claim);
+            self.force_majeure_claim_count = claim_id.saturating_add(1);
+            pending.push(claim_id);
+            self.pending_force_majeure_claims.insert(&clause_id, &pending);
+
+            self.env().emit_event(ForceMajeureClaimFiled {
+                claim_id,
+                seller,
+                clause_id: clause_id.clone(),
+                category,
+                start,
+                end,
+                evidence_hash,
+                response_deadline,
+            });
+            let request_id = self.next_request_id();
+            self.log_clause_call("file_force_majeure_claim", &clause_id, request_id);
+            Ok(claim_id)
+        }
+
+        /// Buyer accepts a pending claim before its response deadline
+        #[ink(message)]
+        pub fn accept_force_majeure_claim(&mut self, claim_id: u64) -> Result<()> {
+            self.respond_to_force_majeure_claim(claim_id, ForceMajeureStatus::Accepted)?;
+            let request_id = self.next_request_id();
+            self.log_function_call("accept_force_majeure_claim", request_id);
+            Ok(())
+        }
+
+        /// Buyer disputes a pending claim before its response deadline; its window stays late
+        #[ink(message)]
+        pub fn dispute_force_majeure_claim(&mut self, claim_id: u64) -> Result<()> {
+            self.respond_to_force_majeure_claim(claim_id, ForceMajeureStatus::Disputed)?;
+            let request_id = self.next_request_id();
+            self.log_function_call("dispute_force_majeure_claim", request_id);
+            Ok(())
+        }
+
+        fn respond_to_force_majeure_claim(
+            &mut self,
+            claim_id: u64,
+            status: ForceMajeureStatus,
+        ) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.buyer {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let timestamp = self.env().block_timestamp();
+            let mut claim = self
+                .force_majeure_claims
+                .get(claim_id)
+                .ok_or(ContractError::InvalidInput)?;
+            if claim.status != ForceMajeureStatus::Pending || timestamp > claim.response_deadline {
+                return Err(ContractError::InvalidInput);
+            }
+            let subject = DisputeSubject::ForceMajeureClaim { claim_id };
+            if self.disputes.open_about(&subject).is_some() {
+                return Err(ContractError::DisputeOpen);
+            }
+
+            claim.status = status;
+            claim.responded_at = Some(timestamp);
+            self.force_majeure_claims.insert(claim_id, &claim);
+            self.close_force_majeure_claim(&claim);
+            self.env().emit_event(ForceMajeureClaimResolved {
+                claim_id,
+                status,
+                timestamp,
+            });
+            Ok(())
+        }
+
+        /// Take an answered claim off its clause's pending list, keeping its window if accepted
+        fn close_force_majeure_claim(&mut self, claim: &ForceMajeureClaim) {
+            let mut pending =
+                self.pending_force_majeure_claims.get(&claim.clause_id).unwrap_or_default();
+            pending.retain(|claim_id| *claim_id != claim.claim_id);
+            self.pending_force_majeure_claims.insert(&claim.clause_id, &pending);
+            if claim.is_accepted() {
+                self.add_force_majeure_window(&claim.clause_id, claim.start, claim.end);
+            }
+        }
+
+        fn add_force_majeure_window(&mut self, clause_id: &str, start: u64, end: u64) {
+            let mut windows = self.force_majeure_windows.get(clause_id).unwrap_or_default();
+            windows.push((start, end));
+            windows.sort_unstable();
+            let mut merged: Vec<Window> = Vec::with_capacity(windows.len());
+            for (start, end) in windows {
+                match merged.last_mut() {
+                    Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                    _ => merged.push((start, end)),
+                }
+            }
+            self.force_majeure_windows.insert(clause_id, &merged);
+        }
+
+        /// Record a clause's pending claims whose response deadline has passed as deemed
+        /// accepted
+        fn settle_force_majeure_claims(&mut self, clause_id: &str, now: u64) {
+            let pending = self.pending_force_majeure_claims.get(clause_id).unwrap_or_default();
+            for claim_id in pending {
+                let Some(mut claim) = self.force_majeure_claims.get(claim_id) else {
+                    continue;
+                };
+                if claim.status != ForceMajeureStatus::Pending || now <= claim.response_deadline {
+                    continue;
+                }
+
+                claim.status = ForceMajeureStatus::DeemedAccepted;
+                self.force_majeure_claims.insert(claim_id, &claim);
+                self.close_force_majeure_claim(&claim);
+                self.env().emit_event(ForceMajeureClaimResolved {
+                    claim_id,
+                    status: claim.status,
+                    timestamp: now,
+                });
+                let old_str = format!("{}: {:?}", claim_id, ForceMajeureStatus::Pending);
+                let new_str = format!("{}: {:?}", claim_id, claim.status);
+                self.log_field_change("force_majeure_claim", &old_str, &new_str);
+            }
+        }
+
+        /// A claim as it stands at `now`, treating an unanswered claim past its deadline as
+        /// deemed accepted even before that is recorded
+        fn force_majeure_claim_at(&self, claim_id: u64, now: u64) -> Option<ForceMajeureClaim> {
+            let mut claim = self.force_majeure_claims.get(claim_id)?;
+            if claim.status == ForceMajeureStatus::Pending && now > claim.response_deadline {
+                claim.status = ForceMajeureStatus::DeemedAccepted;
+            }
+            Some(claim)
+        }
+
+        /// Milliseconds of `(agreed, delivered]` covered by a clause's accepted claims,
+        /// counting time covered by overlapping claims once
+        fn force_majeure_excluded(
+            &self,
+            clause_id: &str,
+            agreed: u64,
+            delivered: u64,
+            now: u64,
+        ) -> u64 {
+            let deemed_accepted = self
+                .pending_force_majeure_claims
+                .get(clause_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|claim_id| self.force_majeure_claim_at(claim_id, now))
+                .filter(ForceMajeureClaim::is_accepted)
+                .map(|claim| (claim.start, claim.end));
+            let mut windows: Vec<Window> = self
+                .force_majeure_windows
+                .get(clause_id)
+                .unwrap_or_default()
+                .into_iter()
+                .chain(deemed_accepted)
+                .map(|(start, end)| (start.max(agreed), end.min(delivered)))
+                .filter(|(start, end)| start < end)
+                .collect();
+            windows.sort_unstable();
+
+            let mut excluded = 0;
+            let mut covered_to = agreed;
+            for (start, end) in windows {
+                let start = start.max(covered_to);
+                if end > start {
+                    excluded += end - start;
+                    covered_to = end;
+                }
+            }
+            excluded
+        }
+
+        #[ink(message)]
+        pub fn get_force_majeure_claim(&self, claim_id: u64) -> Option<ForceMajeureClaim> {
+            self.force_majeure_claim_at(claim_id, self.env().block_timestamp())
+        }
+
+        #[ink(message)]
+        pub fn get_force_majeure_claims(&self, start: u64, limit: u64) -> Vec<ForceMajeureClaim> {
+            let now = self.env().block_timestamp();
+            let end = start.saturating_add(limit).min(self.force_majeure_claim_count);
+            (start..end)
+                .filter_map(|claim_id| self.force_majeure_claim_at(claim_id, now))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_force_majeure_claim_count(&self) -> u64 {
+            self.force_majeure_claim_count
+        }
+
+        #[ink(message)]
+        pub fn get_force_majeure_response_period(&self) -> u64 {
+            self.force_majeure_response_period
+        }
+
+        /// Milliseconds the buyer has to respond to claims filed from now on
+        #[ink(message)]
+        pub fn set_force_majeure_response_period(&mut self, new_value: u64) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if new_value == 0 {
+                return Err(ContractError::InvalidInput);
+            }
+
+            if self.force_majeure_response_period != new_value {
+                let old_str = self.force_majeure_response_period.to_string();
+                let new_str = new_value.to_string();
+                self.log_field_change("force_majeure_response_period", &old_str, &new_str);
+                self.force_majeure_response_period = new_value;
+            }
+            Ok(())
+        }
+
+        // === DISPUTES ===
+
+        /// Buyer or seller asks the arbitrator to decide a disagreement, freezing the
+        /// operations it affects until it is resolved. A resolved subject can be disputed again
+        /// only after `DISPUTE_COOLDOWN`. Returns the dispute id.
+        #[ink(message)]
+        pub fn open_dispute(
+            &mut self,
+            subject: DisputeSubject,
+            evidence_hash: Hash,
+        ) -> Result<u64> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.buyer && caller != self.seller {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if let DisputeSubject::Penalty { clause_id } = &subject {
+                if self.terminations.contains(clause_id) {
+                    return Err(ContractError::ContractTerminated);
+                }
+            }
+
+            let valid_subject = match &subject {
+                DisputeSubject::Penalty { clause_id } => self.clauses.contains(clause_id),
+                DisputeSubject::ForceMajeureClaim { claim_id } => self
+                    .force_majeure_claims
+                    .get(claim_id)
+                    .is_some_and(|claim| claim.status == ForceMajeureStatus::Disputed),
+            };
+            if !valid_subject || evidence_hash == Hash::default() {
+                return Err(ContractError::InvalidInput);
+            }
+            let now = self.env().block_timestamp();
+            let dispute_id = self
+                .disputes
+                .open(subject.clone(), caller, evidence_hash, now)
+                .ok_or(ContractError::InvalidInput)?
+                .dispute_id;
+
+            self.env().emit_event(DisputeOpened {
+                dispute_id,
+                opened_by: caller,
+                subject: subject.clone(),
+                evidence_hash,
+            });
+            let request_id = self.next_request_id();
+            match &subject {
+                DisputeSubject::Penalty { clause_id } => {
+                    self.log_clause_call("open_dispute", clause_id, request_id)
+                }
+                DisputeSubject::ForceMajeureClaim { .. } => {
+                    self.log_function_call("open_dispute", request_id)
+                }
+            }
+            Ok(dispute_id)
+        }
+
+        /// Arbitrator settles an open dispute with an outcome that fits its subject.
+        ///
+        /// Disputes can still be resolved after termination, so frozen funds are not stuck.
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, dispute_id: u64, outcome: DisputeOutcome) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let arbitrator = self.env().caller();
+            if arbitrator != self.arbitrator {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let dispute = self.disputes.get(dispute_id).ok_or(ContractError::InvalidInput)?;
+            let fits_subject = matches!(
+                (&dispute.subject, &outcome),
+                (_, DisputeOutcome::Dismissed)
+                    | (DisputeSubject::Penalty { .. }, DisputeOutcome::PenaltyOverride { .. })
+                    | (
+                        DisputeSubject::ForceMajeureClaim { .. },
+                        DisputeOutcome::ForceMajeureRuling { .. }
+                    )
+            );
+            if !fits_subject {
+                return Err(ContractError::InvalidInput);
+            }
+
+            // Close the dispute first so the award is not frozen by it
+            let dispute = self
+                .disputes
+                .resolve(dispute_id, outcome.clone(), self.env().block_timestamp())
+                .ok_or(ContractError::InvalidInput)?;
+            let request_id = self.next_request_id();
+            match (&dispute.subject, &outcome) {
+                (
+                    DisputeSubject::Penalty { clause_id },
+                    DisputeOutcome::PenaltyOverride { penalty },
+                ) => {
+                    let old_str = format!("{:?}", self.penalty_overrides.get(clause_id));
+                    let new_str = format!("{:?}", Some(penalty));
+                    self.log_clause_field_change("penalty_override", &old_str, &new_str);
+                    self.penalty_overrides.insert(clause_id, penalty);
+
+                    // Publish the award with the calculation it replaces
+                    let terms = self.clauses.get(clause_id).ok_or(ContractError::ProcessingFailed)?;
+                    let now = self.env().block_timestamp();
+                    let (response, breakdown) = self.calculate_penalty(clause_id, &terms, now)?;
+                    self.reduce_penalty(clause_id, response.penalty)?;
+                    self.assess_penalty(clause_id, response.penalty, request_id)?;
+                    self.emit_penalty_assessed(request_id, clause_id, &response, breakdown);
+                }
+                (
+                    DisputeSubject::ForceMajeureClaim { claim_id },
+                    DisputeOutcome::ForceMajeureRuling { accepted },
+                ) => {
+                    let mut claim = self
+                        .force_majeure_claims
+                        .get(claim_id)
+                        .ok_or(ContractError::ProcessingFailed)?;
+                    if *accepted {
+                        claim.status = ForceMajeureStatus::Accepted;
+                        self.force_majeure_claims.insert(claim_id, &claim);
+                        self.add_force_majeure_window(&claim.clause_id, claim.start, claim.end);
+                        let old_str = format!("{}: {:?}", claim_id, ForceMajeureStatus::Disputed);
+                        let new_str = format!("{}: {:?}", claim_id, claim.status);
+                        self.log_field_change("force_majeure_claim", &old_str, &new_str);
+                    }
+                }
+                _ => {}
+            }
+
+            self.env().emit_event(DisputeResolved {
+                dispute_id,
+                arbitrator,
+                outcome,
+            });
+            match &dispute.subject {
+                DisputeSubject::Penalty { clause_id } => {
+                    self.log_clause_call("resolve_dispute", clause_id, request_id)
+                }
+                DisputeSubject::ForceMajeureClaim { .. } => {
+                    self.log_function_call("resolve_dispute", request_id)
+                }
+            }
+            Ok(())
+        }
+
+        /// Whether claims and termination under a clause wait for the arbitrator
+        fn is_penalty_frozen(&self, clause_id: &str) -> bool {
+            self.disputes
+                .open_disputes()
+                .into_iter()
+                .any(|dispute| match dispute.subject {
+                    DisputeSubject::Penalty { clause_id: disputed } => disputed == clause_id,
+                    DisputeSubject::ForceMajeureClaim { claim_id } => self
+                        .force_majeure_claims
+                        .get(claim_id)
+                        .is_some_and(|claim| claim.clause_id == clause_id),
+                })
+        }
+
+        #[ink(message)]
+        pub fn get_dispute(&self, dispute_id: u64) -> Option<Dispute> {
+            self.disputes.get(dispute_id)
+        }
+
+        /// Dispute history, oldest first
+        #[ink(message)]
+        pub fn get_disputes(&self, start: u64, limit: u64) -> Vec<Dispute> {
+            self.disputes.range(start, limit)
+        }
+
+        #[ink(message)]
+        pub fn get_dispute_count(&self) -> u64 {
+            self.disputes.count()
+        }
+
+        #[ink(message)]
+        pub fn get_open_disputes(&self) -> Vec<Dispute> {
+            self.disputes.open_disputes()
+        }
+
+        #[ink(message)]
+        pub fn get_penalty_override(&self, clause_id: String) -> Option<Decimal> {
+            self.penalty_overrides.get(&clause_id)
+        }
+
+        // === CLAUSES ===
+
+        /// Add the terms for another late delivery clause, e.g. one per purchase order, whose
+        /// goods are due at `agreed_delivery`. Both parties must first agree to its goods value
+        /// with `agree_goods_value`.
+        #[ink(message)]
+        pub fn add_clause(
+            &mut self,
+            clause_id: String,
+            terms: ClauseTerms,
+            agreed_delivery: u64,
+        ) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if clause_id.is_empty()
+                || self.clauses.contains(&clause_id)
+                || self.clause_ids.len() as u32 >= MAX_CLAUSES
+            {
+                return Err(ContractError::InvalidInput);
+            }
+            Self::validate_terms(&terms)?;
+            self.take_goods_value_agreements(&clause_id, terms.goods_value)?;
+
+            self.insert_clause(clause_id.clone(), &terms, agreed_delivery);
+            self.env().emit_event(ClauseAdded {
+                clause_id: clause_id.clone(),
+                added_by: caller,
+            });
+
+            let request_id = self.next_request_id();
+            self.log_clause_call("add_clause", &clause_id, request_id);
+            Ok(())
+        }
+
+        /// Store a new clause with nothing delivered yet
+        fn insert_clause(&mut self, clause_id: String, terms: &ClauseTerms, agreed_delivery: u64) {
+            let delivery = DeliveryRecord {
+                agreed_delivery,
+                dispatched_at: None,
+                received_at: None,
+                confirmed_by: None,
+            };
+            self.clauses.insert(&clause_id, terms);
+            self.deliveries.insert(&clause_id, &delivery);
+            self.clause_ids.push(clause_id);
+        }
+
+        /// Replace a clause's terms, logging each field that changes. A new goods value must
+        /// first be agreed by both parties with `agree_goods_value`.
+        #[ink(message)]
+        pub fn amend_clause(&mut self, clause_id: String, terms: ClauseTerms) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let current = self.clauses.get(&clause_id).ok_or(ContractError::InvalidInput)?;
+            Self::validate_terms(&terms)?;
+            if current.goods_value != terms.goods_value {
+                self.take_goods_value_agreements(&clause_id, terms.goods_value)?;
+            }
+
+            if current.force_majeure != terms.force_majeure {
+                let old_str = current.force_majeure.to_string();
+                let new_str = terms.force_majeure.to_string();
+                self.log_clause_field_change("force_majeure", &old_str, &new_str);
+            }
+            if current.penalty_duration != terms.penalty_duration {
+                let old_str = current.penalty_duration.to_string();
+                let new_str = terms.penalty_duration.to_string();
+                self.log_clause_field_change("penalty_duration", &old_str, &new_str);
+            }
+            if current.penalty_percentage != terms.penalty_percentage {
+                let old_str = current.penalty_percentage.to_string();
+                let new_str = terms.penalty_percentage.to_string();
+                self.log_clause_field_change("penalty_percentage", &old_str, &new_str);
+            }
+            if current.cap_percentage != terms.cap_percentage {
+                let old_str = current.cap_percentage.to_string();
+                let new_str = terms.cap_percentage.to_string();
+                self.log_clause_field_change("cap_percentage", &old_str, &new_str);
+            }
+            if current.termination != terms.termination {
+                let old_str = current.termination.to_string();
+                let new_str = terms.termination.to_string();
+                self.log_clause_field_change("termination", &old_str, &new_str);
+            }
+            if current.fractional_part != terms.fractional_part {
+                let old_str = current.fractional_part.to_string();
+                let new_str = terms.fractional_part.to_string();
+                self.log_clause_field_change("fractional_part", &old_str, &new_str);
+            }
+            if current.lateness_counting != terms.lateness_counting {
+                let old_str = format!("{:?}", current.lateness_counting);
+                let new_str = format!("{:?}", terms.lateness_counting);
+                self.log_clause_field_change("lateness_counting", &old_str, &new_str);
+            }
+            if current.goods_value != terms.goods_value {
+                let old_str = current.goods_value.to_string();
+                let new_str = terms.goods_value.to_string();
+                self.log_clause_field_change("goods_value", &old_str, &new_str);
+            }
+
+            self.clauses.insert(&clause_id, &terms);
+
+            // Record when the amendment leaves the latest agreed document out of date
+            if let Some(document) = self.get_latest_document(clause_id.clone()) {
+                if document.template_data_hash != self.template_data_hash(&clause_id) {
+                    let old_str = hex_string(document.document_hash.as_ref());
+                    self.log_clause_field_change("document", &old_str, "");
+                }
+            }
+
+            let request_id = self.next_request_id();
+            self.log_clause_call("amend_clause", &clause_id, request_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_clause(&self, clause_id: String) -> Option<ClauseTerms> {
+            self.clauses.get(&clause_id)
+        }
+
+        /// Buyer or seller agrees to the goods value of a clause being added or amended. The
+        /// owner can set it once both parties have agreed to the same value.
+        #[ink(message)]
+        pub fn agree_goods_value(&mut self, clause_id: String, goods_value: Decimal) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.buyer && caller != self.seller {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if clause_id.is_empty() {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let key = (clause_id.clone(), caller);
+            let old_str = self
+                .goods_value_agreements
+                .get(&key)
+                .map(|agreed| agreed.to_string())
+                .unwrap_or_default();
+            self.goods_value_agreements.insert(&key, &goods_value);
+            self.log_clause_field_change("agreed_goods_value", &old_str, &goods_value.to_string());
+
+            let request_id = self.next_request_id();
+            self.log_clause_call("agree_goods_value", &clause_id, request_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_agreed_goods_value(
+            &self,
+            clause_id: String,
+            party: AccountId,
+        ) -> Option<Decimal> {
+            self.goods_value_agreements.get(&(clause_id, party))
+        }
+
+        /// Consume both parties' agreement to `goods_value` for a clause
+        fn take_goods_value_agreements(
+            &mut self,
+            clause_id: &str,
+            goods_value: Decimal,
+        ) -> Result<()> {
+            let parties = [self.buyer, self.seller];
+            for party in parties {
+                let key = (clause_id.to_string(), party);
+                if self.goods_value_agreements.get(&key) != Some(goods_value) {
+                    return Err(ContractError::Unauthorized);
+                }
+            }
+            for party in parties {
+                self.goods_value_agreements.remove(&(clause_id.to_string(), party));
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_clause_ids(&self) -> Vec<String> {
+            self.clause_ids.clone()
+        }
+
+        // === DRAFTING ===
+
+        /// Ask the draft service to render a clause from its current terms in the given format.
+        /// Returns the draft request id.
+        #[ink(message)]
+        pub fn request_draft(
+            &mut self,
+            clause_id: String,
+            output_format: DraftFormat,
+        ) -> Result<u64> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner && caller != self.buyer && caller != self.seller {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let template_data = self
+                .get_template_data(clause_id.clone())
+                .ok_or(ContractError::InvalidInput)?;
+            let request_id = self.next_request_id();
+
+            self.env().emit_event(DraftRequested {
+                requester: caller,
+                request_id,
+                template_data,
+                timestamp: self.env().block_timestamp(),
+                output_format,
+                template_class: TEMPLATE_CLASS.to_string(),
+                namespace: TEMPLATE_NAMESPACE.to_string(),
+            });
+
+            self.log_clause_call("request_draft", &clause_id, request_id);
+            Ok(request_id)
+        }
+
+        /// A clause's terms as Concerto JSON template data
+        #[ink(message)]
+        pub fn get_template_data(&self, clause_id: String) -> Option<String> {
+            let terms = self.clauses.get(&clause_id)?;
+            let duration = |duration: &Duration| {
+                format!(
+                    "{{\"$class\":\"{}\",\"amount\":{},\"unit\":\"{}\"}}",
+                    DURATION_CLASS, duration.amount, duration.unit
+                )
+            };
+
+            Some(format!(
+                "{{\"$class\":\"{}\",\"clauseId\":{},\"forceMajeure\":{},\"penaltyDuration\":{},\
+                 \"penaltyPercentage\":{},\"capPercentage\":{},\"termination\":{},\
+                 \"fractionalPart\":\"{}\"}}",
+                TEMPLATE_CLASS,
+                json_string(&clause_id),
+                terms.force_majeure,
+                duration(&terms.penalty_duration),
+                terms.penalty_percentage,
+                terms.cap_percentage,
+                duration(&terms.termination),
+                terms.fractional_part,
+            ))
+        }
+
+        // === DOCUMENTS ===
+
+        /// Register the hash of a document rendered from a clause's current terms. Returns the
+        /// document's version for the clause.
+        #[ink(message)]
+        pub fn register_document(
+            &mut self,
+            clause_id: String,
+            document_hash: Hash,
+            algorithm: DocumentHashAlgorithm,
+        ) -> Result<u32> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.terminations.contains(&clause_id) {
+                return Err(ContractError::ContractTerminated);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner && caller != self.buyer && caller != self.seller {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if !self.clauses.contains(&clause_id) || self.documents.contains(document_hash) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let version = self.document_counts.get(&clause_id).unwrap_or(0).saturating_add(1);
+            let template_data_hash = self.template_data_hash(&clause_id);
+            let record = DocumentRecord {
+                clause_id: clause_id.clone(),
+                version,
+                document_hash,
+                algorithm,
+                template_data_hash,
+                submitter: caller,
+                timestamp: self.env().block_timestamp(),
+            };
+            self.documents.insert(document_hash, &record);
+            self.document_versions.insert((clause_id.clone(), version), &document_hash);
+            self.document_counts.insert(&clause_id, &version);
+
+            self.env().emit_event(DocumentRegistered {
+                document_hash,
+                clause_id: clause_id.clone(),
+                version,
+                template_data_hash,
+                submitter: caller,
+            });
+
+            let request_id = self.next_request_id();
+            self.log_clause_call("register_document", &clause_id, request_id);
+            Ok(version)
+        }
+
+        /// Whether a registered document was rendered from its clause's current terms
+        #[ink(message)]
+        pub fn verify_document(&self, document_hash: Hash) -> bool {
+            match self.documents.get(document_hash) {
+                Some(record) => record.template_data_hash == self.template_data_hash(&record.clause_id),
+                None => false,
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_document(&self, document_hash: Hash) -> Option<DocumentRecord> {
+            self.documents.get(document_hash)
+        }
+
+        #[ink(message)]
+        pub fn get_latest_document(&self, clause_id: String) -> Option<DocumentRecord> {
+            let version = self.document_counts.get(&clause_id)?;
+            let document_hash = self.document_versions.get((clause_id, version))?;
+            self.documents.get(document_hash)
+        }
+
+        /// Blake2x256 of a clause's template data; all zeroes for an unknown clause
+        fn template_data_hash(&self, clause_id: &str) -> Hash {
+            self.get_template_data(clause_id.to_string())
+                .map(|template_data| Self::blake2x256(template_data.as_bytes()))
+                .unwrap_or_default()
+        }
+
+        // === PENALTY CALCULATION ===
+
+        /// Penalty periods must be a non-zero length that fits in a timestamp
+        fn penalty_duration_millis(duration: &Duration) -> Result<u64> {
+            duration
+                .to_millis()
+                .filter(|millis| *millis > 0)
+                .ok_or(ContractError::InvalidInput)
+        }
+
+        fn termination_millis(duration: &Duration) -> Result<u64> {
+            duration.to_millis().ok_or(ContractError::InvalidInput)
+        }
+
+        fn validate_terms(terms: &ClauseTerms) -> Result<()> {
+            Self::penalty_duration_millis(&terms.penalty_duration)?;
+            Self::termination_millis(&terms.termination)?;
+            Ok(())
+        }
+
+        /// Milliseconds between a clause's agreed delivery and its recorded receipt, measured up
+        /// to `now` when receipt has not been confirmed yet
+        fn recorded_delay(&self, clause_id: &str, now: u64) -> u64 {
+            let Some(delivery) = self.deliveries.get(clause_id) else {
+                return 0;
+            };
+            let delivered_at = delivery.received_at.unwrap_or(now);
+            delivered_at.saturating_sub(delivery.agreed_delivery)
+        }
+
+        /// Evaluate a late delivery clause against the recorded delivery.
+        ///
+        /// Timestamps are Unix milliseconds. `now` is used as the delivery time when receipt
+        /// has not been confirmed yet. When the clause has a force majeure provision, accepted
+        /// force majeure windows are not counted as late. A penalty awarded by the arbitrator
+        /// replaces the calculated one.
+        fn calculate_penalty(
+            &self,
+            clause_id: &str,
+            terms: &ClauseTerms,
+            now: u64,
+        ) -> Result<(LateDeliveryAndPenaltyResponse, PenaltyBreakdown)> {
+            let penalty_duration = Self::penalty_duration_millis(&terms.penalty_duration)?;
+            let termination = Self::termination_millis(&terms.termination)?;
+            let unit = terms.fractional_part.to_millis();
+            let cap = terms
+                .goods_value
+                .percentage_of(terms.cap_percentage, Rounding::Down)
+                .ok_or(ContractError::ProcessingFailed)?;
+
+            let delivery = self.deliveries.get(clause_id).ok_or(ContractError::InvalidInput)?;
+            let delay = self.recorded_delay(clause_id, now);
+            let agreed = delivery.agreed_delivery;
+            let excluded = if terms.force_majeure {
+                self.force_majeure_excluded(clause_id, agreed, agreed + delay, now)
+            } else {
+                0
+            };
+            let mut breakdown = PenaltyBreakdown {
+                delay,
+                rounded_delay: 0,
+                fractional_part: terms.fractional_part.clone(),
+                lateness_counting: terms.lateness_counting.clone(),
+                periods: 0,
+                uncapped_penalty: Decimal::ZERO,
+                cap,
+                cap_hit: false,
+                force_majeure_applied: excluded > 0,
+                force_majeure_excluded: excluded,
+                penalty_override: self.penalty_overrides.get(clause_id),
+            };
+            // Accepted force majeure time extends the agreed delivery
+            let agreed = agreed + excluded;
+            let delay = delay - excluded;
+            if delay == 0 {
+                let response = LateDeliveryAndPenaltyResponse {
+                    penalty: breakdown.penalty_override.unwrap_or(Decimal::ZERO),
+                    buyer_may_terminate: false,
+                };
+                return Ok((response, breakdown));
+            }
+
+            let rounded_delay = match terms.lateness_counting {
+                // Any fractional part of a unit is considered a full unit
+                LatenessCounting::ElapsedTime => delay.div_ceil(unit).saturating_mul(unit),
+                LatenessCounting::BusinessDays => {
+                    let business_days = self.business_days_late(agreed, agreed + delay);
+                    business_days.saturating_mul(DAY_MILLIS)
+                }
+            };
+            let buyer_may_terminate = rounded_delay >= termination;
+            breakdown.rounded_delay = rounded_delay;
+
+            let periods = u128::from(rounded_delay / penalty_duration);
+            let rate = terms
+                .penalty_percentage
+                .checked_mul_int(periods)
+                .ok_or(ContractError::ProcessingFailed)?;
+            let penalty = terms
+                .goods_value
+                .percentage_of(rate, Rounding::Down)
+                .ok_or(ContractError::ProcessingFailed)?;
+            breakdown.periods = periods;
+            breakdown.uncapped_penalty = penalty;
+            breakdown.cap_hit = penalty > cap;
+
+            let response = LateDeliveryAndPenaltyResponse {
+                penalty: breakdown.penalty_override.unwrap_or(penalty.min(cap)),
+                buyer_may_terminate,
+            };
+            Ok((response, breakdown))
+        }
+
+        // === AUDIT LOG FUNCTIONALITY ===
+
+        /// Record a function call in the audit log, including any pending field changes
+        fn log_function_call(&mut self, function_name: &str, request_id: u64) {
+            self.log_call(function_name, None, request_id);
+        }
+
+        /// Record a function call that acted on a single clause
+        fn log_clause_call(&mut self, function_name: &str, clause_id: &str, request_id: u64) {
+            self.log_call(function_name, Some(clause_id.to_string()), request_id);
+        }
+
+        fn log_call(&mut self, function_name: &str, clause_id: Option<String>, request_id: u64) {
+            let caller = self.env().caller();
+            let timestamp = self.env().block_timestamp();
+
+            // Take all pending field changes and include them in this function call entry
+            let field_changes = core::mem::take(&mut self.pending_field_changes);
+
+            let log_entry = AuditLogEntry::FunctionCall {
+                caller,
+                timestamp,
+                function_name: function_name.to_string(),
+                request_id,
+                clause_id: clause_id.clone(),
+                field_changes: field_changes.clone(),
+            };
+
+            self.append_audit_entry(&log_entry);
+
+            self.env().emit_event(FunctionCalled {
+                caller,
+                function_name: function_name.to_string(),
+                request_id,
+                clause_id: clause_id.clone(),
+                timestamp,
+            });
+
+            // Emit individual field change events for each change
+            for field_change in field_changes {
+                self.env().emit_event(ContractDataChanged {
+                    field_name: field_change.field_name,
+                    changed_by: caller,
+                    clause_id: clause_id.clone(),
+                    old_value: field_change.old_value,
+                    new_value: field_change.new_value,
+                    block_number: self.env().block_number() as u64,
+                    timestamp,
+                });
+            }
+        }
+
+        /// Record a direct field change immediately (for setter functions called directly)
+        fn log_field_change(&mut self, field_name: &str, old_value: &str, new_value: &str) {
+            let caller = self.env().caller();
+            let timestamp = self.env().block_timestamp();
+            let block_number = self.env().block_number() as u64;
+
+            let log_entry = AuditLogEntry::DirectFieldChange {
+                field_name: field_name.to_string(),
+                changed_by: caller,
+                clause_id: None,
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+                block_number,
+                timestamp,
+            };
+
+            self.append_audit_entry(&log_entry);
+
+            self.env().emit_event(ContractDataChanged {
+                field_name: field_name.to_string(),
+                changed_by: caller,
+                clause_id: None,
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+                block_number,
+                timestamp,
+            });
+        }
+
+        /// Record a change to one field of a clause's terms - adds to pending changes for
+        /// inclusion in the next clause call log
+        fn log_clause_field_change(&mut self, field_name: &str, old_value: &str, new_value: &str) {
+            let field_change = FieldChange {
+                field_name: field_name.to_string(),
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+            };
+
+            self.pending_field_changes.push(field_change);
+        }
+
+        /// Store an entry at the next index, extend the hash chain over it and index it
+        fn append_audit_entry(&mut self, log_entry: &AuditLogEntry) {
+            let index = self.audit_log_count;
+            let hash = Self::chain_audit_hash(&self.audit_log_head, log_entry);
+
+            // Store with current count as index, then increment
+            self.audit_log.insert(index, log_entry);
+            self.audit_log_hashes.insert(index, &hash);
+            self.audit_log_head = hash;
+            self.audit_log_count = self.audit_log_count.saturating_add(1);
+
+            let caller = log_entry.caller();
+            let position = self.audit_log_caller_count.get(caller).unwrap_or(0);
+            self.audit_log_by_caller.insert((caller, position), &index);
+            self.audit_log_caller_count.insert(caller, &position.saturating_add(1));
+
+            if let AuditLogEntry::FunctionCall { function_name, .. } = log_entry {
+                let position = self.audit_log_function_count.get(function_name).unwrap_or(0);
+                self.audit_log_by_function.insert((function_name.clone(), position), &index);
+                self.audit_log_function_count.insert(function_name, &position.saturating_add(1));
+            }
+        }
+
+        /// Blake2x256 over the previous hash followed by the SCALE-encoded entry
+        fn chain_audit_hash(previous: &Hash, log_entry: &AuditLogEntry) -> Hash {
+            let mut input = Vec::from(previous.as_ref());
+            scale::Encode::encode_to(log_entry, &mut input);
+            Self::blake2x256(&input)
+        }
+
+        fn blake2x256(input: &[u8]) -> Hash {
+            let mut output = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(input, &mut output);
+            Hash::from(output)
+        }
+
+        /// Hash of the latest audit log entry; all zeroes while the log is empty
+        #[ink(message)]
+        pub fn get_audit_log_head(&self) -> Hash {
+            self.audit_log_head
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_hash(&self, index: u64) -> Option<Hash> {
+            self.audit_log_hashes.get(index)
+        }
+
+        /// Recompute the hash chain over entries `start..end` and check it against the stored
+        /// hashes. Returns false if the range is out of bounds or any entry is missing or altered.
+        #[ink(message)]
+        pub fn verify_audit_range(&self, start: u64, end: u64) -> bool {
+            if start < self.audit_log_start || start > end || end > self.audit_log_count {
+                return false;
+            }
+
+            let mut hash = if start == 0 {
+                Hash::default()
+            } else if start == self.audit_log_start {
+                self.audit_archived_head
+            } else {
+                match self.audit_log_hashes.get(start - 1) {
+                    Some(hash) => hash,
+                    None => return false,
+                }
+            };
+
+            for i in start..end {
+                let Some(entry) = self.audit_log.get(i) else {
+                    return false;
+                };
+                hash = Self::chain_audit_hash(&hash, &entry);
+                if self.audit_log_hashes.get(i) != Some(hash) {
+                    return false;
+                }
+            }
+
+            true
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_count(&self) -> u64 {
+            self.audit_log_count
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log(&self, start: u64, limit: u64) -> Vec<AuditLogEntry> {
+            let mut entries = Vec::new();
+            let end = start.saturating_add(limit).min(self.audit_log_count);
+
+            for i in start..end {
+                if let Some(entry) = self.audit_log.get(i) {
+                    entries.push(entry);
+                }
+            }
+
+            entries
+        }
+
+        /// Entries made by `caller` with timestamps in `from..to`.
+        ///
+        /// Pass `None` as the cursor for the first page, then the returned `next_cursor`.
+        #[ink(message)]
+        pub fn get_audit_log_by_caller(
+            &self,
+            caller: AccountId,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let first = self.audit_log_caller_start.get(caller).unwrap_or(0);
+            let len = self.audit_log_caller_count.get(caller).unwrap_or(0);
+            self.audit_log_page(
+                first..len,
+                |position| self.audit_log_by_caller.get((caller, position)),
+                from,
+                to,
+                cursor,
+                limit,
+            )
+        }
+
+        /// Calls of `function_name` with timestamps in `from..to`, paginated as
+        /// `get_audit_log_by_caller`
+        #[ink(message)]
+        pub fn get_audit_log_by_function(
+            &self,
+            function_name: String,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let first = self.audit_log_function_start.get(&function_name).unwrap_or(0);
+            let len = self.audit_log_function_count.get(&function_name).unwrap_or(0);
+            self.audit_log_page(
+                first..len,
+                |position| self.audit_log_by_function.get((function_name.clone(), position)),
+                from,
+                to,
+                cursor,
+                limit,
+            )
+        }
+
+        /// All entries with timestamps in `from..to`, paginated as `get_audit_log_by_caller`
+        #[ink(message)]
+        pub fn get_audit_log_by_time(
+            &self,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let positions = self.audit_log_start..self.audit_log_count;
+            self.audit_log_page(positions, Some, from, to, cursor, limit)
+        }
+
+        /// Page through the retained `positions` of an index, where `log_index` maps a position
+        /// to its audit log index.
+        ///
+        /// Entries are appended in block order, so timestamps never decrease along the log or
+        /// any index into it. The first page binary searches for `from` and every page stops
+        /// at `to`, so only the returned entries and a logarithmic number of others are read.
+        fn audit_log_page(
+            &self,
+            positions: core::ops::Range<u64>,
+            log_index: impl Fn(u64) -> Option<u64>,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let timestamp_at = |position: u64| {
+                log_index(position)
+                    .and_then(|index| self.audit_log.get(index))
+                    .map(|entry| entry.timestamp())
+            };
+
+            let len = positions.end;
+            let mut position = match cursor {
+                Some(cursor) => cursor.max(positions.start),
+                None => {
+                    let (mut low, mut high) = (positions.start, len);
+                    while low < high {
+                        let mid = low + (high - low) / 2;
+                        if timestamp_at(mid).unwrap_or(0) < from {
+                            low = mid + 1;
+                        } else {
+                            high = mid;
+                        }
+                    }
+                    low
+                }
+            };
+
+            let mut entries = Vec::new();
+            while position < len {
+                if entries.len() as u64 >= limit {
+                    return AuditLogPage {
+                        entries,
+                        next_cursor: Some(position),
+                    };
+                }
+                if let Some(index) = log_index(position) {
+                    if let Some(entry) = self.audit_log.get(index) {
+                        if entry.timestamp() >= to {
+                            break;
+                        }
+                        entries.push((index, entry));
+                    }
+                }
+                position = position.saturating_add(1);
+            }
+
+            AuditLogPage {
+                entries,
+                next_cursor: None,
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_function_calls(&self, start: u64, limit: u64) -> Vec<AuditLogEntry> {
+            let mut entries = Vec::new();
+            let mut count = 0u64;
+
+            for i in start..self.audit_log_count {
+                if count >= limit {
+                    break;
+                }
+                if let Some(entry) = self.audit_log.get(i) {
+                    if matches!(entry, AuditLogEntry::FunctionCall { .. }) {
+                        entries.push(entry);
+                        count = count.saturating_add(1);
+                    }
+                }
+            }
+
+            entries
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_field_changes(&self, start: u64, limit: u64) -> Vec<AuditLogEntry> {
+            let mut entries = Vec::new();
+            let mut count = 0u64;
+
+            for i in start..self.audit_log_count {
+                if count >= limit {
+                    break;
+                }
+                if let Some(entry) = self.audit_log.get(i) {
+                    match entry {
+                        AuditLogEntry::DirectFieldChange { .. } => {
+                            entries.push(entry);
+                            count = count.saturating_add(1);
+                        }
+                        AuditLogEntry::FunctionCall {
+                            ref field_changes, ..
+                        } => {
+                            if !field_changes.is_empty() {
+                                entries.push(entry);
+                                count = count.saturating_add(1);
+                            }
+                        }
+                    }
+                }
+            }
+
+            entries
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_field_changes_by_field(
+            &self,
+            field_name: String,
+        ) -> Vec<AuditLogEntry> {
+            let mut matching_entries = Vec::new();
+
+            for i in 0..self.audit_log_count {
+                if let Some(entry) = self.audit_log.get(i) {
+                    match entry {
+                        AuditLogEntry::DirectFieldChange {
+                            field_name: ref entry_field_name,
+                            ..
+                        } => {
+                            if entry_field_name == &field_name {
+                                matching_entries.push(entry);
+                            }
+                        }
+                        AuditLogEntry::FunctionCall {
+                            ref field_changes, ..
+                        } => {
+                            for field_change in field_changes {
+                                if field_change.field_name == field_name {
+                                    matching_entries.push(entry.clone());
+                                    break; // Only add the entry once even if multiple matching fields
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            matching_entries
+        }
+
+        // === AUDIT LOG RETENTION ===
+
+        #[ink(message)]
+        pub fn set_audit_retention(&mut self, new_value: AuditRetention) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if new_value.max_entries == Some(0) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let old_value = format!("{:?}", self.audit_retention);
+            let new_value_str = format!("{:?}", new_value);
+            self.log_field_change("audit_retention", &old_value, &new_value_str);
+            self.audit_retention = new_value;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_audit_retention(&self) -> AuditRetention {
+            self.audit_retention.clone()
+        }
+
+        /// Archive up to `limit` of the oldest entries the retention policy allows to be
+        /// pruned: commit them to a Merkle root checkpoint, then remove them from storage.
+        ///
+        /// Returns the number of entries archived. Nothing is archived when the policy
+        /// retains every entry.
+        #[ink(message)]
+        pub fn archive_audit_log(&mut self, limit: u64) -> Result<u64> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let start = self.audit_log_start;
+            let now = self.env().block_timestamp();
+            let max_end = start
+                .saturating_add(limit.min(MAX_AUDIT_ARCHIVE_BATCH))
+                .min(self.audit_log_count);
+
+            // Entries beyond the retained count, and then entries past the maximum age; both
+            // form a prefix of the log because timestamps never decrease
+            let mut end = match self.audit_retention.max_entries {
+                Some(max_entries) => self.audit_log_count.saturating_sub(max_entries),
+                None => 0,
+            }
+            .clamp(start, max_end);
+            if let Some(max_age) = self.audit_retention.max_age {
+                while end < max_end {
+                    match self.audit_log.get(end) {
+                        Some(entry) if now.saturating_sub(entry.timestamp()) > max_age => {
+                            end += 1
+                        }
+                        _ => break,
+                    }
+                }
+            }
+            if end == start {
+                return Ok(0);
+            }
+
+            let mut leaves = Vec::new();
+            for index in start..end {
+                let entry = self.audit_log.take(index).ok_or(ContractError::ProcessingFailed)?;
+                leaves.push(Self::audit_leaf_hash(index, &entry));
+                self.prune_audit_indexes(&entry);
+            }
+            let head = self
+                .audit_log_hashes
+                .get(end - 1)
+                .ok_or(ContractError::ProcessingFailed)?;
+            for index in start..end {
+                self.audit_log_hashes.remove(index);
+            }
+
+            let checkpoint_id = self.audit_checkpoint_count;
+            let root = Self::audit_merkle_root(leaves);
+            let checkpoint = AuditCheckpoint {
+                start,
+                end,
+                root,
+                head,
+                archived_by: caller,
+                archived_at: now,
+            };
+            self.audit_checkpoints.insert(checkpoint_id, &checkpoint);
+            self.audit_checkpoint_count = self.audit_checkpoint_count.saturating_add(1);
+            self.audit_log_start = end;
+            self.audit_archived_head = head;
+
+            self.env().emit_event(AuditLogArchived {
+                checkpoint_id,
+                start,
+                end,
+                root,
+            });
+            Ok(end - start)
+        }
+
+        /// Drop a pruned entry's positions from the caller and function indexes. Entries are
+        /// pruned in log order, so each is the oldest retained position of its index.
+        fn prune_audit_indexes(&mut self, log_entry: &AuditLogEntry) {
+            let caller = log_entry.caller();
+            let position = self.audit_log_caller_start.get(caller).unwrap_or(0);
+            self.audit_log_by_caller.remove((caller, position));
+            self.audit_log_caller_start.insert(caller, &position.saturating_add(1));
+
+            if let AuditLogEntry::FunctionCall { function_name, .. } = log_entry {
+                let position = self.audit_log_function_start.get(function_name).unwrap_or(0);
+                self.audit_log_by_function.remove((function_name.clone(), position));
+                self.audit_log_function_start.insert(function_name, &position.saturating_add(1));
+            }
+        }
+
+        /// Merkle leaf for an archived entry: Blake2x256 over `0x00` and the SCALE-encoded
+        /// `(index, entry)`
+        fn audit_leaf_hash(index: u64, log_entry: &AuditLogEntry) -> Hash {
+            let mut input = Vec::from([0u8]);
+            scale::Encode::encode_to(&(index, log_entry), &mut input);
+            Self::blake2x256(&input)
+        }
+
+        /// Merkle node: Blake2x256 over `0x01` and both children
+        fn audit_node_hash(left: &Hash, right: &Hash) -> Hash {
+            let mut input = Vec::from([1u8]);
+            input.extend_from_slice(left.as_ref());
+            input.extend_from_slice(right.as_ref());
+            Self::blake2x256(&input)
+        }
+
+        /// Root over `leaves`, pairing neighbours level by level and carrying an unpaired
+        /// last node up unchanged
+        fn audit_merkle_root(mut level: Vec<Hash>) -> Hash {
+            while level.len() > 1 {
+                level = level
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [left, right] => Self::audit_node_hash(left, right),
+                        _ => pair[0],
+                    })
+                    .collect();
+            }
+            level.first().copied().unwrap_or_default()
+        }
+
+        /// Check that `entry` was archived at `index` under a checkpoint. `proof` lists the
+        /// sibling hashes from the leaf up to the root, skipping levels where the node is
+        /// carried up unpaired.
+        #[ink(message)]
+        pub fn verify_audit_proof(
+            &self,
+            checkpoint_id: u64,
+            index: u64,
+            entry: AuditLogEntry,
+            proof: Vec<Hash>,
+        ) -> bool {
+            let Some(checkpoint) = self.audit_checkpoints.get(checkpoint_id) else {
+                return false;
+            };
+            if index < checkpoint.start || index >= checkpoint.end {
+                return false;
+            }
+
+            let mut position = index - checkpoint.start;
+            let mut width = checkpoint.end - checkpoint.start;
+            let mut hash = Self::audit_leaf_hash(index, &entry);
+            let mut siblings = proof.iter();
+            while width > 1 {
+                if position % 2 == 1 {
+                    let Some(sibling) = siblings.next() else {
+                        return false;
+                    };
+                    hash = Self::audit_node_hash(sibling, &hash);
+                } else if position + 1 < width {
+                    let Some(sibling) = siblings.next() else {
+                        return false;
+                    };
+                    hash = Self::audit_node_hash(&hash, sibling);
+                }
+                position /= 2;
+                width = width.div_ceil(2);
+            }
+
+            siblings.next().is_none() && hash == checkpoint.root
+        }
+
+        #[ink(message)]
+        pub fn get_audit_checkpoint(&self, checkpoint_id: u64) -> Option<AuditCheckpoint> {
+            self.audit_checkpoints.get(checkpoint_id)
+        }
+
+        #[ink(message)]
+        pub fn get_audit_checkpoint_count(&self) -> u64 {
+            self.audit_checkpoint_count
+        }
+
+        /// Index of the oldest entry still in storage
+        #[ink(message)]
+        pub fn get_audit_log_start(&self) -> u64 {
+            self.audit_log_start
+        }
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::bool_assert_comparison)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn default_works() {
+            let contract = LateDeliveryAndPenalty::default();
+            assert_eq!(contract.is_paused(), false);
+        }
+
+        #[ink::test]
+        fn pause_works() {
+            let mut contract = LateDeliveryAndPenalty::default();
+            assert_eq!(contract.pause(), Ok(()));
+            assert_eq!(contract.is_paused(), true);
+        }
+
+        #[ink::test]
+        fn unpause_works() {
+            let mut contract = LateDeliveryAndPenalty::default();
+            assert_eq!(contract.pause(), Ok(()));
+            assert_eq!(contract.unpause(), Ok(()));
+            assert_eq!(contract.is_paused(), false);
+        }
+
+        type Env = ink::env::DefaultEnvironment;
+
+        const AGREED_DELIVERY: u64 = 1703980800000;
+        const CLAUSE_ID: &str = "late-delivery-clause-1";
+        const SECOND: u64 = 1000;
+        const DAY: u64 = 86400 * SECOND;
+
+        fn clause_id() -> String {
+            CLAUSE_ID.to_string()
+        }
+
+        fn whole(value: u128) -> Decimal {
+            Decimal::from_integer(value).unwrap()
+        }
+
+        fn days(amount: u128) -> Duration {
+            Duration {
+                amount,
+                unit: TemporalUnit::Days,
+            }
+        }
+
+        /// Deploy as alice (owner) with charlie as buyer, bob as seller and django as
+        /// arbitrator, then act as the buyer
+        fn clause(
+            force_majeure: bool,
+            penalty_duration: Duration,
+            penalty_percentage: Decimal,
+            cap_percentage: Decimal,
+            termination: Duration,
+            fractional_part: TemporalUnit,
+        ) -> LateDeliveryAndPenalty {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            let contract = LateDeliveryAndPenalty::new(
+                accounts.charlie,
+                accounts.bob,
+                accounts.django,
+                AGREED_DELIVERY,
+                clause_id(),
+                force_majeure,
+                penalty_duration,
+                penalty_percentage,
+                cap_percentage,
+                termination,
+                fractional_part,
+                whole(1000000),
+            )
+            .expect("valid terms");
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            contract
+        }
+
+        /// Terms from `deployments/test-params.md`
+        fn test_params_terms() -> ClauseTerms {
+            ClauseTerms {
+                force_majeure: false,
+                penalty_duration: days(1),
+                penalty_percentage: whole(10),
+                cap_percentage: whole(55),
+                termination: days(14),
+                fractional_part: TemporalUnit::Days,
+                lateness_counting: LatenessCounting::ElapsedTime,
+                goods_value: whole(1000000),
+            }
+        }
+
+        fn test_params_contract(force_majeure: bool) -> LateDeliveryAndPenalty {
+            clause(
+                force_majeure,
+                days(1),
+                whole(10),
+                whole(55),
+                days(14),
+                TemporalUnit::Days,
+            )
+        }
+
+        fn request() -> LateDeliveryAndPenaltyRequest {
+            LateDeliveryAndPenaltyRequest {
+                force_majeure: false,
+                agreed_delivery: AGREED_DELIVERY,
+                delivered_at: None,
+                goods_value: whole(1000000),
+            }
+        }
+
+        /// Buyer and seller agree to a goods value for `clause`, leaving alice as the caller
+        fn agree_goods_value(
+            contract: &mut LateDeliveryAndPenalty,
+            clause: &str,
+            goods_value: Decimal,
+        ) {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            for party in [accounts.charlie, accounts.bob] {
+                ink::env::test::set_caller::<Env>(party);
+                assert_eq!(contract.agree_goods_value(clause.to_string(), goods_value), Ok(()));
+            }
+            ink::env::test::set_caller::<Env>(accounts.alice);
+        }
+
+        /// Buyer and seller agree to a new goods value for the clause, which alice then sets,
+        /// leaving charlie as the caller
+        fn set_goods_value(contract: &mut LateDeliveryAndPenalty, goods_value: Decimal) {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            agree_goods_value(contract, &clause_id(), goods_value);
+            let mut terms = contract.get_clause(clause_id()).unwrap();
+            terms.goods_value = goods_value;
+            assert_eq!(contract.amend_clause(clause_id(), terms), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+        }
+
+        /// Buyer confirms receipt at `timestamp`
+        fn deliver_at(contract: &mut LateDeliveryAndPenalty, timestamp: u64) {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            ink::env::test::set_block_timestamp::<Env>(timestamp);
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.confirm_receipt(clause_id()), Ok(()));
+        }
+
+        #[ink::test]
+        fn one_second_late_counts_as_full_day() {
+            let mut contract = test_params_contract(false);
+            deliver_at(&mut contract, AGREED_DELIVERY + SECOND);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(100000));
+            assert!(!response.buyer_may_terminate);
+        }
+
+        #[ink::test]
+        fn on_time_delivery_has_no_penalty() {
+            let mut contract = test_params_contract(false);
+            deliver_at(&mut contract, AGREED_DELIVERY);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(0));
+            assert!(!response.buyer_may_terminate);
+        }
+
+        #[ink::test]
+        fn penalty_is_capped() {
+            let mut contract = test_params_contract(false);
+            deliver_at(&mut contract, AGREED_DELIVERY + 7 * DAY);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(550000));
+            assert!(!response.buyer_may_terminate);
+        }
+
+        #[ink::test]
+        fn buyer_may_terminate_once_termination_reached() {
+            let mut contract = test_params_contract(false);
+            deliver_at(&mut contract, AGREED_DELIVERY + 14 * DAY);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(550000));
+            assert!(response.buyer_may_terminate);
+        }
+
+        #[ink::test]
+        fn accepted_force_majeure_windows_are_not_late() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let evidence = Hash::from([7; 32]);
+            let mut contract = test_params_contract(true);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + DAY);
+
+            // Only the seller files claims, and only the buyer responds to them
+            let window = (AGREED_DELIVERY + DAY, AGREED_DELIVERY + 2 * DAY);
+            let category = ForceMajeureCategory::NaturalDisaster;
+            let file = |contract: &mut LateDeliveryAndPenalty, clause_id, (start, end)| {
+                contract.file_force_majeure_claim(clause_id, category.clone(), start, end, evidence)
+            };
+            assert_eq!(file(&mut contract, clause_id(), window), Err(ContractError::Unauthorized));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let reversed = (window.1, window.0);
+            let invalid = Err(ContractError::InvalidInput);
+            assert_eq!(file(&mut contract, clause_id(), reversed), invalid);
+            let unknown = "unknown".to_string();
+            assert_eq!(file(&mut contract, unknown, window), invalid);
+            let accepted = file(&mut contract, clause_id(), window).unwrap();
+            let disputed = file(&mut contract, clause_id(), (window.1, window.1 + DAY)).unwrap();
+            assert_eq!(
+                contract.accept_force_majeure_claim(accepted),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.accept_force_majeure_claim(accepted), Ok(()));
+            assert_eq!(contract.dispute_force_majeure_claim(disputed), Ok(()));
+            assert_eq!(
+                contract.accept_force_majeure_claim(disputed),
+                Err(ContractError::InvalidInput)
+            );
+
+            // A request can no longer waive the penalty by asserting force majeure itself
+            deliver_at(&mut contract, AGREED_DELIVERY + 3 * DAY);
+            let mut claim = request();
+            claim.force_majeure = true;
+            let preview = contract.preview_penalty(clause_id(), claim).unwrap();
+            assert_eq!(preview.response.penalty, whole(200000));
+            assert!(preview.breakdown.force_majeure_applied);
+            assert_eq!(preview.breakdown.force_majeure_excluded, DAY);
+
+            // An unanswered claim is deemed accepted once the response period has passed, and
+            // time covered by overlapping claims is only excluded once
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let overlapping = (AGREED_DELIVERY + DAY / 2, window.0 + DAY / 2);
+            let unanswered = file(&mut contract, clause_id(), overlapping).unwrap();
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 3 * DAY + 7 * DAY + 1);
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(
+                contract.accept_force_majeure_claim(unanswered),
+                Err(ContractError::InvalidInput)
+            );
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(200000));
+            let claim = contract.get_force_majeure_claim(unanswered).unwrap();
+            assert_eq!(claim.status, ForceMajeureStatus::DeemedAccepted);
+            let field = "force_majeure_claim".to_string();
+            assert_eq!(contract.get_audit_log_field_changes_by_field(field).len(), 1);
+            let statuses = contract
+                .get_force_majeure_claims(0, 10)
+                .into_iter()
+                .map(|claim| claim.status)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                statuses,
+                vec![
+                    ForceMajeureStatus::Accepted,
+                    ForceMajeureStatus::Disputed,
+                    ForceMajeureStatus::DeemedAccepted,
+                ]
+            );
+
+            // Without a force majeure provision in the clause, accepted claims are ignored
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let claim_id = file(&mut contract, clause_id(), window).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.accept_force_majeure_claim(claim_id), Ok(()));
+            deliver_at(&mut contract, AGREED_DELIVERY + 3 * DAY);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(300000));
+        }
+
+        #[ink::test]
+        fn pending_force_majeure_claims_are_bounded_per_clause() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let evidence = Hash::from([7; 32]);
+            let mut contract = test_params_contract(true);
+            let other = "po-2".to_string();
+            let terms = test_params_terms();
+            agree_goods_value(&mut contract, &other, terms.goods_value);
+            assert_eq!(contract.add_clause(other.clone(), terms, AGREED_DELIVERY), Ok(()));
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY);
+            let window = (AGREED_DELIVERY, AGREED_DELIVERY + DAY);
+            let file = |contract: &mut LateDeliveryAndPenalty, clause_id| {
+                let category = ForceMajeureCategory::Other;
+                contract.file_force_majeure_claim(clause_id, category, window.0, window.1, evidence)
+            };
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let claims = (0..MAX_PENDING_FORCE_MAJEURE_CLAIMS)
+                .map(|_| file(&mut contract, clause_id()).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(file(&mut contract, clause_id()), Err(ContractError::InvalidInput));
+            assert!(file(&mut contract, other).is_ok());
+
+            // Answered claims, rejected ones included, no longer count towards the bound
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.dispute_force_majeure_claim(claims[0]), Ok(()));
+            assert_eq!(contract.accept_force_majeure_claim(claims[1]), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert!(file(&mut contract, clause_id()).is_ok());
+            assert!(file(&mut contract, clause_id()).is_ok());
+            assert_eq!(file(&mut contract, clause_id()), Err(ContractError::InvalidInput));
+
+            // So do claims deemed accepted once the clause is next evaluated
+            deliver_at(&mut contract, AGREED_DELIVERY + 3 * DAY);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 8 * DAY);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(200000));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert!(file(&mut contract, clause_id()).is_ok());
+            let count = MAX_PENDING_FORCE_MAJEURE_CLAIMS as u64 + 4;
+            assert_eq!(contract.get_force_majeure_claim_count(), count);
+        }
+
+        #[ink::test]
+        fn undelivered_goods_use_block_timestamp() {
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 2 * DAY + SECOND);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(300000));
+        }
+
+        #[ink::test]
+        fn partial_periods_are_not_penalised() {
+            let mut contract = clause(
+                false,
+                days(3),
+                whole(10),
+                whole(55),
+                days(20),
+                TemporalUnit::Days,
+            );
+            deliver_at(&mut contract, AGREED_DELIVERY + 4 * DAY);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(100000));
+        }
+
+        #[ink::test]
+        fn fractional_part_rounds_to_its_unit() {
+            let mut contract = clause(
+                false,
+                Duration {
+                    amount: 6,
+                    unit: TemporalUnit::Hours,
+                },
+                whole(10),
+                whole(55),
+                days(14),
+                TemporalUnit::Hours,
+            );
+            // 6h 1s late rounds up to 7 hours, which is one whole 6 hour period
+            let delivered_at = AGREED_DELIVERY + 6 * 3600 * SECOND + SECOND;
+            deliver_at(&mut contract, delivered_at);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(100000));
+        }
+
+        #[ink::test]
+        fn invalid_durations_are_rejected() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            let mut terms = test_params_terms();
+            terms.penalty_duration = days(0);
+            assert_eq!(
+                contract.amend_clause(clause_id(), terms),
+                Err(ContractError::InvalidInput)
+            );
+            let mut terms = test_params_terms();
+            terms.termination = days(u128::from(u64::MAX));
+            assert_eq!(
+                contract.amend_clause(clause_id(), terms),
+                Err(ContractError::InvalidInput)
+            );
+            let mut terms = test_params_terms();
+            terms.penalty_duration = days(2);
+            assert_eq!(contract.amend_clause(clause_id(), terms), Ok(()));
+            assert_eq!(
+                contract.get_clause(clause_id()).unwrap().penalty_duration,
+                days(2)
+            );
+        }
+
+        #[ink::test]
+        fn constructor_rejects_zero_penalty_duration() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let deployed = LateDeliveryAndPenalty::new(
+                accounts.charlie,
+                accounts.bob,
+                accounts.django,
+                AGREED_DELIVERY,
+                clause_id(),
+                false,
+                days(0),
+                whole(10),
+                whole(55),
+                days(14),
+                TemporalUnit::Days,
+                whole(1000000),
+            );
+            assert_eq!(deployed.err(), Some(ContractError::InvalidInput));
+        }
+
+        #[ink::test]
+        fn default_has_no_clauses() {
+            // The default constructor makes the deployer every party, but adds no clauses
+            let mut unconfigured = LateDeliveryAndPenalty::default();
+            assert!(unconfigured.get_clause_ids().is_empty());
+            assert_eq!(
+                unconfigured.late_delivery_and_penalty(clause_id(), request()),
+                Err(ContractError::InvalidInput)
+            );
+        }
+
+        #[ink::test]
+        fn durations_convert_to_millis() {
+            assert_eq!(days(1).to_millis(), Some(DAY));
+            assert_eq!(
+                Duration {
+                    amount: 2,
+                    unit: TemporalUnit::Weeks,
+                }
+                .to_millis(),
+                Some(14 * DAY)
+            );
+            assert_eq!(TemporalUnit::Minutes.to_millis(), 60 * SECOND);
+            assert_eq!(days(3).to_string(), "3 days");
+        }
+
+        #[ink::test]
+        fn fractional_percentages_are_exact() {
+            // Terms from `test-template-data.json`
+            let mut contract = clause(
+                false,
+                days(3),
+                Decimal::from_parts(125, 1).unwrap(),
+                whole(60),
+                days(20),
+                TemporalUnit::Days,
+            );
+            set_goods_value(&mut contract, Decimal::from_parts(123456789, 2).unwrap());
+            deliver_at(&mut contract, AGREED_DELIVERY + 6 * DAY);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, Decimal::from_parts(30864197250, 5).unwrap());
+            assert_eq!(response.penalty.to_string(), "308641.9725");
+        }
+
+        fn balance_of(account: AccountId) -> Balance {
+            ink::env::test::get_account_balance::<Env>(account).unwrap_or_default()
+        }
+
+        /// Contract with test-params terms and a bond from bob in favour of charlie
+        fn bonded_contract(bond: Balance) -> LateDeliveryAndPenalty {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            ink::env::test::transfer_in::<Env>(bond);
+            assert_eq!(contract.deposit_bond(clause_id()), Ok(()));
+            ink::env::test::set_value_transferred::<Env>(0);
+            contract
+        }
+
+        #[ink::test]
+        fn penalty_is_paid_from_bond_to_buyer() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+            let buyer_balance = balance_of(accounts.charlie);
+
+            // Fractions of the smallest token unit are not paid out
+            set_goods_value(&mut contract, Decimal::from_parts(10000005, 1).unwrap());
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + SECOND);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + 100_000);
+
+            // Re-evaluating the same delay does not pay twice
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + 100_000);
+
+            // A longer delay only pays the difference
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 2 * DAY);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + 200_000);
+
+            let bond = contract.get_bond(clause_id()).unwrap();
+            assert_eq!(bond.amount, 800_000);
+            assert_eq!(bond.penalty_paid, 200_000);
+
+            // Each increase is its own assessment, settled in full by the bond
+            assert_eq!(contract.get_penalty_assessment_count(), 2);
+            let second = contract.get_penalty_assessment(1).unwrap();
+            assert_eq!((second.amount, second.paid), (100_000, 100_000));
+            let ledger = contract.get_penalty_ledger(clause_id());
+            assert_eq!((ledger.assessed, ledger.paid, ledger.outstanding()), (200_000, 200_000, 0));
+        }
+
+        #[ink::test]
+        fn bond_smaller_than_penalty_pays_what_it_covers() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(50_000);
+            let buyer_balance = balance_of(accounts.charlie);
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + SECOND);
+            let preview = contract.preview_penalty(clause_id(), request()).unwrap();
+            assert_eq!(preview.payout, 50_000);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + 50_000);
+            let bond = contract.get_bond(clause_id()).unwrap();
+            assert_eq!((bond.amount, bond.penalty_paid), (0, 50_000));
+
+            // The rest is owed on the assessment and paid in instalments
+            let assessment = contract.get_penalty_assessment(0).unwrap();
+            assert_eq!((assessment.amount, assessment.paid), (100_000, 50_000));
+            assert_eq!(contract.get_penalty_ledger(clause_id()).outstanding(), 50_000);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            ink::env::test::transfer_in::<Env>(50_000);
+            assert_eq!(contract.pay_penalty_assessment(0), Ok(()));
+            ink::env::test::set_value_transferred::<Env>(0);
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + 100_000);
+            assert_eq!(contract.get_penalty_ledger(clause_id()).outstanding(), 0);
+
+            // A longer delay is still assessed with the bond exhausted
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 2 * DAY);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            let second = contract.get_penalty_assessment(1).unwrap();
+            assert_eq!((second.amount, second.paid), (100_000, 0));
+        }
+
+        #[ink::test]
+        fn bond_is_released_to_seller_by_buyer() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+            let seller_balance = balance_of(accounts.bob);
+
+            // The bond is held until receipt is recorded
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.release_bond(clause_id()), Err(ContractError::InvalidInput));
+
+            deliver_at(&mut contract, AGREED_DELIVERY + SECOND);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(contract.release_bond(clause_id()), Err(ContractError::Unauthorized));
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.release_bond(clause_id()), Ok(900_000));
+            assert_eq!(balance_of(accounts.bob), seller_balance + 900_000);
+            assert_eq!(contract.release_bond(clause_id()), Err(ContractError::InvalidInput));
+
+            // Nothing more is paid once the bond has been released
+            set_goods_value(&mut contract, whole(3000000));
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            assert_eq!(contract.get_bond(clause_id()).unwrap().penalty_paid, 100_000);
+        }
+
+        #[ink::test]
+        fn seller_releases_bond_after_final_assessment() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+            let seller_balance = balance_of(accounts.bob);
+            let buyer_balance = balance_of(accounts.charlie);
+
+            // The buyer never claims, so releasing assesses the penalty for the recorded receipt
+            deliver_at(&mut contract, AGREED_DELIVERY + SECOND);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.release_bond(clause_id()), Ok(900_000));
+            assert_eq!(balance_of(accounts.bob), seller_balance + 900_000);
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + 100_000);
+            let ledger = contract.get_penalty_ledger(clause_id());
+            assert_eq!((ledger.assessed, ledger.paid), (100_000, 100_000));
+            assert_eq!(penalty_assessed_events().len(), 1);
+        }
+
+        #[ink::test]
+        fn bond_deposit_requires_seller_and_value() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            assert_eq!(contract.deposit_bond(clause_id()), Err(ContractError::Unauthorized));
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.deposit_bond(clause_id()), Err(ContractError::InvalidInput));
+        }
+
+        #[ink::test]
+        fn roles_are_set_at_construction() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let contract = test_params_contract(false);
+            assert_eq!(contract.get_owner(), accounts.alice);
+            assert_eq!(contract.get_buyer(), accounts.charlie);
+            assert_eq!(contract.get_seller(), accounts.bob);
+        }
+
+        #[ink::test]
+        fn only_buyer_submits_claims() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            let claim = request();
+
+            for account in [accounts.alice, accounts.bob] {
+                ink::env::test::set_caller::<Env>(account);
+                assert_eq!(
+                    contract.late_delivery_and_penalty(clause_id(), claim.clone()),
+                    Err(ContractError::Unauthorized)
+                );
+            }
+        }
+
+        #[ink::test]
+        fn seller_acknowledges_latest_claim() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + SECOND);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            let claim_id = contract.get_last_claim(clause_id()).unwrap();
+
+            assert_eq!(
+                contract.acknowledge_claim(clause_id(), claim_id),
+                Err(ContractError::Unauthorized)
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(
+                contract.acknowledge_claim(clause_id(), claim_id + 1),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(contract.acknowledge_claim(clause_id(), claim_id), Ok(()));
+            assert_eq!(contract.get_acknowledged_claim(clause_id()), Some(claim_id));
+        }
+
+        #[ink::test]
+        fn owner_administers_terms_only() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            assert_eq!(
+                contract.amend_clause(clause_id(), test_params_terms()),
+                Err(ContractError::Unauthorized)
+            );
+            assert_eq!(
+                contract.add_clause("po-2".to_string(), test_params_terms(), AGREED_DELIVERY),
+                Err(ContractError::Unauthorized)
+            );
+            assert_eq!(contract.pause(), Err(ContractError::Unauthorized));
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.amend_clause(clause_id(), test_params_terms()), Ok(()));
+        }
+
+        #[ink::test]
+        fn delivery_lifecycle_is_recorded() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            assert_eq!(contract.get_agreed_delivery(clause_id()), Some(AGREED_DELIVERY));
+
+            assert_eq!(contract.record_dispatch(clause_id()), Err(ContractError::Unauthorized));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY - DAY);
+            assert_eq!(contract.record_dispatch(clause_id()), Ok(()));
+            assert_eq!(contract.record_dispatch(clause_id()), Err(ContractError::InvalidInput));
+            assert_eq!(contract.confirm_receipt(clause_id()), Err(ContractError::Unauthorized));
+
+            deliver_at(&mut contract, AGREED_DELIVERY + SECOND);
+            assert_eq!(contract.confirm_receipt(clause_id()), Err(ContractError::InvalidInput));
+            assert_eq!(
+                contract.get_delivery(clause_id()).unwrap(),
+                DeliveryRecord {
+                    agreed_delivery: AGREED_DELIVERY,
+                    dispatched_at: Some(AGREED_DELIVERY - DAY),
+                    received_at: Some(AGREED_DELIVERY + SECOND),
+                    confirmed_by: Some(accounts.charlie),
+                }
+            );
+        }
+
+        #[ink::test]
+        fn request_timestamps_are_ignored() {
+            let mut contract = test_params_contract(false);
+            deliver_at(&mut contract, AGREED_DELIVERY + SECOND);
+
+            // Claiming an on-time delivery does not override the recorded receipt
+            let mut claim = request();
+            claim.agreed_delivery = AGREED_DELIVERY + DAY;
+            claim.delivered_at = Some(AGREED_DELIVERY);
+            let response = contract.late_delivery_and_penalty(clause_id(), claim).unwrap();
+            assert_eq!(response.penalty, whole(100000));
+
+            // Later evaluations keep using the receipt time, not the block time
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 5 * DAY);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(100000));
+        }
+
+        #[ink::test]
+        fn attestor_records_dispatch_and_receipt() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            assert_eq!(
+                contract.set_attestor(Some(accounts.django)),
+                Err(ContractError::Unauthorized)
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.set_attestor(Some(accounts.django)), Ok(()));
+            assert_eq!(contract.get_attestor(), Some(accounts.django));
+
+            ink::env::test::set_caller::<Env>(accounts.django);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY - DAY);
+            assert_eq!(contract.record_dispatch(clause_id()), Ok(()));
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY);
+            assert_eq!(contract.confirm_receipt(clause_id()), Ok(()));
+            let delivery = contract.get_delivery(clause_id()).unwrap();
+            assert_eq!(delivery.dispatched_at, Some(AGREED_DELIVERY - DAY));
+            assert_eq!(delivery.confirmed_by, Some(accounts.django));
+        }
+
+        #[ink::test]
+        fn settlement_agent_records_penalties_settled_elsewhere() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.set_settlement_agent(Some(accounts.django)), Ok(()));
+            assert_eq!(contract.get_settlement_agent(), Some(accounts.django));
+            assert_eq!(contract.set_attestor(Some(accounts.eve)), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(
+                contract.set_settlement_agent(Some(accounts.charlie)),
+                Err(ContractError::Unauthorized)
+            );
+            for delay in [SECOND, 2 * DAY] {
+                ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + delay);
+                assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            }
+            assert_eq!(contract.get_penalty_ledger(clause_id()).outstanding(), 200_000);
+
+            // Only the settlement agent records settlements, not the attestor, and never beyond
+            // what is outstanding
+            for caller in [accounts.charlie, accounts.eve] {
+                ink::env::test::set_caller::<Env>(caller);
+                assert_eq!(
+                    contract.record_penalty_settlement(clause_id(), 150_000),
+                    Err(ContractError::Unauthorized)
+                );
+            }
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(
+                contract.record_penalty_settlement(clause_id(), 200_001),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(contract.record_penalty_settlement(clause_id(), 150_000), Ok(()));
+
+            // The oldest assessment is settled first
+            let assessments = contract.get_penalty_assessments(0, 2);
+            assert_eq!((assessments[0].paid, assessments[1].paid), (100_000, 50_000));
+            let ledger = contract.get_penalty_ledger(clause_id());
+            assert_eq!((ledger.paid, ledger.outstanding()), (150_000, 50_000));
+        }
+
+        /// Register django, eve and frank as oracles with a 2-of-3 threshold
+        fn oracle_contract() -> LateDeliveryAndPenalty {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            for oracle in [accounts.django, accounts.eve, accounts.frank] {
+                assert_eq!(contract.add_oracle(oracle), Ok(()));
+            }
+            assert_eq!(contract.set_oracle_threshold(2), Ok(()));
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 5 * DAY);
+            contract
+        }
+
+        #[ink::test]
+        fn oracles_are_managed_by_owner() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = oracle_contract();
+            assert_eq!(contract.get_oracles().len(), 3);
+            assert_eq!(contract.add_oracle(accounts.eve), Err(ContractError::InvalidInput));
+            assert_eq!(contract.set_oracle_threshold(4), Err(ContractError::InvalidInput));
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.remove_oracle(accounts.eve), Err(ContractError::Unauthorized));
+
+            // Two oracles must remain while the threshold is two
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.remove_oracle(accounts.eve), Ok(()));
+            assert_eq!(contract.remove_oracle(accounts.frank), Err(ContractError::InvalidInput));
+            assert_eq!(contract.set_oracle_threshold(1), Ok(()));
+            assert_eq!(contract.remove_oracle(accounts.frank), Ok(()));
+            assert_eq!(contract.get_oracles(), vec![accounts.django]);
+
+            // The last oracle can be replaced but not removed
+            assert_eq!(contract.remove_oracle(accounts.django), Err(ContractError::InvalidInput));
+            assert_eq!(contract.add_oracle(accounts.eve), Ok(()));
+            assert_eq!(contract.remove_oracle(accounts.django), Ok(()));
+            assert_eq!(contract.get_oracles(), vec![accounts.eve]);
+        }
+
+        #[ink::test]
+        fn attestations_reaching_threshold_record_receipt() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = oracle_contract();
+            let log_count = contract.get_audit_log_count();
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(
+                contract.submit_attestation(clause_id(), AGREED_DELIVERY),
+                Err(ContractError::Unauthorized)
+            );
+
+            // Disagreeing attestations do not reach the threshold
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(contract.submit_attestation(clause_id(), AGREED_DELIVERY + DAY), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.eve);
+            assert_eq!(contract.submit_attestation(clause_id(), AGREED_DELIVERY + 2 * DAY), Ok(()));
+            assert_eq!(contract.get_delivery(clause_id()).unwrap().received_at, None);
+
+            ink::env::test::set_caller::<Env>(accounts.frank);
+            assert_eq!(contract.submit_attestation(clause_id(), AGREED_DELIVERY + 2 * DAY), Ok(()));
+            let delivery = contract.get_delivery(clause_id()).unwrap();
+            assert_eq!(delivery.received_at, Some(AGREED_DELIVERY + 2 * DAY));
+            assert_eq!(delivery.confirmed_by, Some(accounts.frank));
+            assert_eq!(
+                contract.submit_attestation(clause_id(), AGREED_DELIVERY + DAY),
+                Err(ContractError::InvalidInput)
+            );
+
+            let entries = contract.get_audit_log(log_count, 10);
+            assert_eq!(entries.len(), 3);
+            assert!(entries
+                .iter()
+                .all(|entry| matches!(
+                    entry,
+                    AuditLogEntry::FunctionCall { function_name, .. }
+                        if function_name == "submit_attestation"
+                )));
+            // Each entry records the time its oracle attested
+            assert!(matches!(
+                &entries[1],
+                AuditLogEntry::FunctionCall { caller, field_changes, .. }
+                    if *caller == accounts.eve
+                        && field_changes[0].field_name == "attestation"
+                        && field_changes[0].new_value == (AGREED_DELIVERY + 2 * DAY).to_string()
+            ));
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(200000));
+        }
+
+        #[ink::test]
+        fn attestations_within_tolerance_agree() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = oracle_contract();
+            let minute = 60 * SECOND;
+            assert_eq!(contract.get_attestation_tolerance(), 15 * minute);
+            assert_eq!(
+                contract.set_attestation_tolerance(DAY + 1),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(contract.set_attestation_tolerance(10 * minute), Ok(()));
+
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(contract.submit_attestation(clause_id(), AGREED_DELIVERY + DAY), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.eve);
+            assert_eq!(
+                contract.submit_attestation(clause_id(), AGREED_DELIVERY + DAY + 11 * minute),
+                Ok(())
+            );
+            assert_eq!(contract.get_delivery(clause_id()).unwrap().received_at, None);
+
+            // Revising within the tolerance agrees, and the earlier of the two is the median
+            assert_eq!(
+                contract.submit_attestation(clause_id(), AGREED_DELIVERY + DAY + 4 * minute),
+                Ok(())
+            );
+            let delivery = contract.get_delivery(clause_id()).unwrap();
+            assert_eq!(delivery.received_at, Some(AGREED_DELIVERY + DAY));
+        }
+
+        #[ink::test]
+        fn attestations_cannot_be_in_the_future() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = oracle_contract();
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(
+                contract.submit_attestation(clause_id(), AGREED_DELIVERY + 6 * DAY),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(contract.get_attestation(clause_id(), accounts.django), None);
+        }
+
+        #[ink::test]
+        fn termination_requires_threshold_delay() {
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 13 * DAY);
+            assert_eq!(
+                contract.terminate(clause_id(), request()),
+                Err(ContractError::TerminationNotReached)
+            );
+
+            // Receipt fixes the delay, so waiting longer does not help
+            deliver_at(&mut contract, AGREED_DELIVERY + 13 * DAY);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 20 * DAY);
+            assert_eq!(
+                contract.terminate(clause_id(), request()),
+                Err(ContractError::TerminationNotReached)
+            );
+            assert!(!contract.is_terminated(clause_id()));
+        }
+
+        #[ink::test]
+        fn buyer_terminates_and_bond_is_settled() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+            let buyer_balance = balance_of(accounts.charlie);
+            let seller_balance = balance_of(accounts.bob);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 14 * DAY);
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.terminate(clause_id(), request()), Err(ContractError::Unauthorized));
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let response = contract.terminate(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(550000));
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + 550_000);
+            assert_eq!(balance_of(accounts.bob), seller_balance + 450_000);
+
+            assert!(contract.is_terminated(clause_id()));
+            assert_eq!(
+                contract.get_termination_record(clause_id()),
+                Some(TerminationRecord {
+                    clause_id: clause_id(),
+                    terminated_at: AGREED_DELIVERY + 14 * DAY,
+                    delay: 14 * DAY,
+                    penalty: whole(550000),
+                })
+            );
+            assert!(contract.get_bond(clause_id()).unwrap().released);
+        }
+
+        #[ink::test]
+        fn terminated_clause_rejects_changes() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            agree_goods_value(&mut contract, "po-2", whole(1000000));
+            let later = AGREED_DELIVERY + 10 * DAY;
+            assert_eq!(contract.add_clause("po-2".to_string(), test_params_terms(), later), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 15 * DAY);
+            assert!(contract.terminate(clause_id(), request()).is_ok());
+
+            assert_eq!(
+                contract.terminate(clause_id(), request()),
+                Err(ContractError::ContractTerminated)
+            );
+            assert_eq!(
+                contract.late_delivery_and_penalty(clause_id(), request()),
+                Err(ContractError::ContractTerminated)
+            );
+            assert_eq!(
+                contract.confirm_receipt(clause_id()),
+                Err(ContractError::ContractTerminated)
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            let mut terms = test_params_terms();
+            terms.cap_percentage = whole(10);
+            assert_eq!(
+                contract.amend_clause(clause_id(), terms.clone()),
+                Err(ContractError::ContractTerminated)
+            );
+            assert_eq!(contract.get_clause(clause_id()), Some(test_params_terms()));
+
+            // The other clause and the contract's settings are unaffected
+            assert_eq!(contract.amend_clause("po-2".to_string(), terms), Ok(()));
+            assert_eq!(contract.add_oracle(accounts.django), Ok(()));
+            assert!(!contract.is_terminated("po-2".to_string()));
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let response = contract
+                .late_delivery_and_penalty("po-2".to_string(), request())
+                .unwrap();
+            // Five days late under the amended 10% cap
+            assert_eq!(response.penalty, whole(100000));
+            assert!(!response.buyer_may_terminate);
+        }
+
+        #[ink::test]
+        fn requests_are_stored_with_unique_ids() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + SECOND);
+            let first = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+
+            // A second claim in the same block gets its own id, and its goods value is ignored
+            let mut larger = request();
+            larger.goods_value = whole(2000000);
+            let second = contract.late_delivery_and_penalty(clause_id(), larger.clone()).unwrap();
+            assert_eq!(second, first);
+
+            assert_eq!(contract.get_request_count(), 2);
+            assert_eq!(contract.get_last_claim(clause_id()), Some(1));
+            assert_eq!(contract.get_request(0).unwrap().response, first);
+            assert_eq!(
+                contract.get_request(1),
+                Some(RequestRecord {
+                    request_id: 1,
+                    clause_id: clause_id(),
+                    submitter: accounts.charlie,
+                    timestamp: AGREED_DELIVERY + SECOND,
+                    request: larger,
+                    response: second,
+                })
+            );
+            assert_eq!(contract.get_requests(0, 1).len(), 1);
+            assert_eq!(contract.get_requests(0, 10).len(), 2);
+            assert_eq!(contract.get_request(2), None);
+        }
+
+        #[ink::test]
+        fn preview_matches_execution() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 2 * DAY + SECOND);
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let buyer_balance = balance_of(accounts.charlie);
+            let log_count = contract.get_audit_log_count();
+            let request_count = contract.get_request_count();
+
+            let preview = contract.preview_penalty(clause_id(), request()).unwrap();
+            assert_eq!(
+                preview.breakdown,
+                PenaltyBreakdown {
+                    delay: 2 * DAY + SECOND,
+                    rounded_delay: 3 * DAY,
+                    fractional_part: TemporalUnit::Days,
+                    lateness_counting: LatenessCounting::ElapsedTime,
+                    periods: 3,
+                    uncapped_penalty: whole(300000),
+                    cap: whole(550000),
+                    cap_hit: false,
+                    force_majeure_applied: false,
+                    force_majeure_excluded: 0,
+                    penalty_override: None,
+                }
+            );
+            assert_eq!(preview.payout, 300_000);
+            assert_eq!(contract.get_audit_log_count(), log_count);
+            assert_eq!(contract.get_request_count(), request_count);
+
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response, preview.response);
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + preview.payout);
+
+            // Once paid, the same delay previews no further payout
+            assert_eq!(contract.preview_penalty(clause_id(), request()).unwrap().payout, 0);
+        }
+
+        fn penalty_assessed_events() -> Vec<PenaltyAssessed> {
+            ink::env::test::recorded_events()
+                .filter_map(|event| {
+                    <PenaltyAssessed as scale::Decode>::decode(&mut &event.data[..]).ok()
+                })
+                .collect()
+        }
+
+        #[ink::test]
+        fn penalty_decisions_are_published_in_full() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 2 * DAY + SECOND);
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let preview = contract.preview_penalty(clause_id(), request()).unwrap();
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+
+            let events = penalty_assessed_events();
+            assert_eq!(events.len(), 1);
+            assert_eq!(Some(events[0].request_id), contract.get_last_claim(clause_id()));
+            assert_eq!(events[0].clause_id, clause_id());
+            assert_eq!(events[0].breakdown, preview.breakdown);
+            assert_eq!(events[0].penalty, response.penalty);
+            assert!(!events[0].buyer_may_terminate);
+
+            // An award by the arbitrator is published with the calculation it replaces
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let subject = DisputeSubject::Penalty {
+                clause_id: clause_id(),
+            };
+            let dispute_id = contract.open_dispute(subject, Hash::from([9; 32])).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.django);
+            let award = DisputeOutcome::PenaltyOverride {
+                penalty: whole(400000),
+            };
+            assert_eq!(contract.resolve_dispute(dispute_id, award), Ok(()));
+
+            let events = penalty_assessed_events();
+            assert_eq!(events.len(), 2);
+            assert_eq!(events[1].request_id, contract.get_request_count() - 1);
+            assert_eq!(events[1].penalty, whole(400000));
+            assert_eq!(events[1].breakdown.penalty_override, Some(whole(400000)));
+            assert_eq!(events[1].breakdown.uncapped_penalty, preview.breakdown.uncapped_penalty);
+            assert_eq!(contract.get_penalty_ledger(clause_id()).paid, 400_000);
+        }
+
+        #[ink::test]
+        fn lower_award_writes_off_then_credits_back() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(100_000);
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 2 * DAY + SECOND);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 3 * DAY + SECOND);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            ink::env::test::transfer_in::<Env>(50_000);
+            assert_eq!(contract.pay_penalty_assessment(1), Ok(()));
+            ink::env::test::set_value_transferred::<Env>(0);
+
+            // 400,000 was assessed and 150,000 paid, but the arbitrator awards only 50,000
+            let subject = DisputeSubject::Penalty {
+                clause_id: clause_id(),
+            };
+            let dispute_id = contract.open_dispute(subject, Hash::from([9; 32])).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.django);
+            let award = DisputeOutcome::PenaltyOverride {
+                penalty: whole(50000),
+            };
+            assert_eq!(contract.resolve_dispute(dispute_id, award), Ok(()));
+
+            // Everything outstanding is written off, and the rest credited back, newest first
+            let assessments = contract.get_penalty_assessments(0, 10);
+            let totals = assessments
+                .iter()
+                .map(|assessment| (assessment.paid, assessment.written_off, assessment.credited))
+                .collect::<Vec<_>>();
+            assert_eq!(totals, vec![(100_000, 200_000, 50_000), (50_000, 50_000, 50_000)]);
+            assert_eq!(
+                contract.get_penalty_ledger(clause_id()),
+                PenaltyLedger {
+                    assessed: 400_000,
+                    acknowledged: 0,
+                    paid: 150_000,
+                    written_off: 250_000,
+                    credited: 100_000,
+                    reduced: 350_000,
+                }
+            );
+            let topic = <PenaltyCredited as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+            let credited = ink::env::test::recorded_events()
+                .filter(|event| event.topics.first().is_some_and(|first| first[..] == topic))
+                .count();
+            assert_eq!(credited, 2);
+
+            // Later claims are measured against the award, not what was first assessed
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 4 * DAY + SECOND);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(50000));
+            assert_eq!(contract.get_penalty_assessment_count(), 2);
+        }
+
+        #[ink::test]
+        fn preview_reports_cap_and_errors() {
+            let contract = test_params_contract(false);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 8 * DAY);
+            let preview = contract.preview_penalty(clause_id(), request()).unwrap();
+            assert!(preview.breakdown.cap_hit);
+            assert_eq!(preview.breakdown.uncapped_penalty, whole(800000));
+            assert_eq!(preview.response.penalty, whole(550000));
+            assert_eq!(preview.payout, 0);
+
+            // The bond pays what it holds
+            let mut contract = bonded_contract(100_000);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + 8 * DAY);
+            assert_eq!(contract.preview_penalty(clause_id(), request()).unwrap().payout, 100_000);
+
+            // Like a claim, the preview waits for a disputed penalty to be decided
+            let accounts = ink::env::test::default_accounts::<Env>();
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let subject = DisputeSubject::Penalty {
+                clause_id: clause_id(),
+            };
+            assert!(contract.open_dispute(subject, Hash::from([9; 32])).is_ok());
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(
+                contract.preview_penalty(clause_id(), request()),
+                Err(ContractError::DisputeOpen)
+            );
+            assert_eq!(
+                contract.late_delivery_and_penalty(clause_id(), request()),
+                Err(ContractError::DisputeOpen)
+            );
+        }
+
+        #[ink::test]
+        fn clauses_are_added_and_read_by_id() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            let mut terms = test_params_terms();
+            terms.penalty_percentage = Decimal::from_parts(125, 1).unwrap();
+
+            // The goods value of a new clause needs both parties' agreement
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(
+                contract.add_clause("po-2".to_string(), terms.clone(), AGREED_DELIVERY),
+                Err(ContractError::Unauthorized)
+            );
+            agree_goods_value(&mut contract, "po-2", terms.goods_value);
+            assert_eq!(
+                contract.add_clause("po-2".to_string(), terms.clone(), AGREED_DELIVERY),
+                Ok(())
+            );
+            assert_eq!(
+                contract.add_clause("po-2".to_string(), terms.clone(), AGREED_DELIVERY),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(
+                contract.add_clause(String::new(), terms.clone(), AGREED_DELIVERY),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(
+                contract.amend_clause("po-3".to_string(), terms.clone()),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(contract.get_clause("po-2".to_string()), Some(terms));
+            assert_eq!(contract.get_clause_ids(), vec![clause_id(), "po-2".to_string()]);
+
+            // Logged after each party's agreement to the goods value
+            let entries = contract.get_audit_log(0, 10);
+            assert!(matches!(
+                &entries[2],
+                AuditLogEntry::FunctionCall { function_name, clause_id: Some(clause_id), .. }
+                    if function_name == "add_clause" && clause_id == "po-2"
+            ));
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(
+                contract.late_delivery_and_penalty("po-3".to_string(), request()),
+                Err(ContractError::InvalidInput)
+            );
+        }
+
+        #[ink::test]
+        fn goods_value_is_agreed_by_both_parties() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+
+            // An inflated goods value in the request does not raise the penalty
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + SECOND);
+            let mut inflated = request();
+            inflated.goods_value = whole(1_000_000_000);
+            let response = contract.late_delivery_and_penalty(clause_id(), inflated).unwrap();
+            assert_eq!(response.penalty, whole(100000));
+            assert_eq!(contract.get_bond(clause_id()).unwrap().penalty_paid, 100_000);
+
+            // Only the parties can agree, and the owner cannot change it with one agreement
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(
+                contract.agree_goods_value(clause_id(), whole(2000000)),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.agree_goods_value(clause_id(), whole(2000000)), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.agree_goods_value(clause_id(), whole(1500000)), Ok(()));
+            let mut terms = test_params_terms();
+            terms.goods_value = whole(2000000);
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(
+                contract.amend_clause(clause_id(), terms.clone()),
+                Err(ContractError::Unauthorized)
+            );
+
+            // Other terms can still be amended without the parties
+            let mut unchanged_value = test_params_terms();
+            unchanged_value.cap_percentage = whole(60);
+            assert_eq!(contract.amend_clause(clause_id(), unchanged_value), Ok(()));
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.agree_goods_value(clause_id(), whole(2000000)), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.amend_clause(clause_id(), terms.clone()), Ok(()));
+            assert_eq!(contract.get_clause(clause_id()).unwrap().goods_value, whole(2000000));
+
+            // Agreements are used up by the amendment
+            assert_eq!(contract.get_agreed_goods_value(clause_id(), accounts.charlie), None);
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(200000));
+        }
+
+        #[ink::test]
+        fn penalties_are_evaluated_and_paid_per_clause() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+            let mut terms = test_params_terms();
+            terms.penalty_percentage = whole(20);
+            agree_goods_value(&mut contract, "po-2", terms.goods_value);
+            assert_eq!(contract.add_clause("po-2".to_string(), terms, AGREED_DELIVERY), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            ink::env::test::transfer_in::<Env>(500_000);
+            assert_eq!(contract.deposit_bond("po-2".to_string()), Ok(()));
+            ink::env::test::set_value_transferred::<Env>(0);
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + SECOND);
+            let first = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            let second = contract
+                .late_delivery_and_penalty("po-2".to_string(), request())
+                .unwrap();
+            assert_eq!(first.penalty, whole(100000));
+            assert_eq!(second.penalty, whole(200000));
+
+            // Each clause is paid from its own bond
+            assert_eq!(contract.get_penalty_paid(clause_id()), 100_000);
+            assert_eq!(contract.get_penalty_paid("po-2".to_string()), 200_000);
+            assert_eq!(contract.get_bond(clause_id()).unwrap().penalty_paid, 100_000);
+            let bond = contract.get_bond("po-2".to_string()).unwrap();
+            assert_eq!((bond.amount, bond.penalty_paid), (300_000, 200_000));
+            let last_claim = contract.get_last_claim("po-2".to_string()).unwrap();
+            assert_eq!(contract.get_request(last_claim).unwrap().clause_id, "po-2");
+
+            // Claims are acknowledged per clause, and a later claim under one clause leaves
+            // the other's pending
+            let first_claim = contract.get_last_claim(clause_id()).unwrap();
+            assert_ne!(first_claim, last_claim);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(
+                contract.acknowledge_claim("po-2".to_string(), first_claim),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(contract.acknowledge_claim(clause_id(), first_claim), Ok(()));
+            assert_eq!(contract.get_acknowledged_claim(clause_id()), Some(first_claim));
+            assert_eq!(contract.get_acknowledged_claim("po-2".to_string()), None);
+        }
+
+        fn date(year: u32, month: u32, day: u32) -> CalendarDate {
+            CalendarDate {
+                year,
+                month: Month::from_number(month).unwrap(),
+                day,
+            }
+        }
+
+        #[ink::test]
+        fn calendar_dates_cross_month_and_year_boundaries() {
+            assert_eq!(CalendarDate::from_day_number(0), date(1970, 1, 1));
+            assert_eq!(Day::from_day_number(0), Day::Thursday);
+            assert_eq!(CalendarDate::from_timestamp(AGREED_DELIVERY), date(2023, 12, 31));
+            assert_eq!(Day::from_timestamp(AGREED_DELIVERY), Day::Sunday);
+            assert_eq!(
+                CalendarDate::from_timestamp(AGREED_DELIVERY + DAY),
+                date(2024, 1, 1)
+            );
+            assert_eq!(Day::from_timestamp(AGREED_DELIVERY + DAY), Day::Monday);
+
+            let leap_day = date(2024, 2, 29);
+            assert_eq!(leap_day.to_day_number(), Some(19782));
+            assert_eq!(Day::from_day_number(19782), Day::Thursday);
+            assert_eq!(CalendarDate::from_day_number(19781), date(2024, 2, 28));
+            assert_eq!(CalendarDate::from_day_number(19783), date(2024, 3, 1));
+            assert_eq!(
+                date(2023, 3, 1).to_day_number(),
+                date(2023, 2, 28).to_day_number().map(|day| day + 1)
+            );
+            assert_eq!(date(2023, 2, 29).to_day_number(), None);
+            assert_eq!(date(1969, 12, 31).to_day_number(), None);
+            assert_eq!(date(2024, 2, 29).to_string(), "2024-02-29");
+
+            for day_number in [0, 10956, 19722, 19723, 19782, 47540] {
+                let calendar_date = CalendarDate::from_day_number(day_number);
+                assert_eq!(calendar_date.to_day_number(), Some(day_number));
+            }
+        }
+
+        #[ink::test]
+        fn calendar_is_managed_by_owner() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            assert_eq!(contract.get_weekend(), vec![Day::Saturday, Day::Sunday]);
+            assert_eq!(
+                contract.add_holiday(date(2024, 1, 1)),
+                Err(ContractError::Unauthorized)
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.add_holiday(date(2024, 12, 25)), Ok(()));
+            assert_eq!(contract.add_holiday(date(2024, 1, 1)), Ok(()));
+            assert_eq!(
+                contract.add_holiday(date(2024, 1, 1)),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(
+                contract.add_holiday(date(2023, 2, 29)),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(
+                contract.get_holidays(),
+                vec![date(2024, 1, 1), date(2024, 12, 25)]
+            );
+            assert_eq!(contract.remove_holiday(date(2024, 12, 25)), Ok(()));
+            assert_eq!(
+                contract.remove_holiday(date(2024, 12, 25)),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(contract.get_holidays(), vec![date(2024, 1, 1)]);
+
+            assert_eq!(
+                contract.set_weekend(vec![Day::Friday, Day::Friday]),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(contract.set_weekend(vec![Day::Friday, Day::Saturday]), Ok(()));
+            assert_eq!(contract.get_weekend(), vec![Day::Friday, Day::Saturday]);
+        }
+
+        /// Test parameters counted in business days, with New Year's Day as a holiday
+        fn business_day_contract() -> LateDeliveryAndPenalty {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            let mut terms = test_params_terms();
+            terms.lateness_counting = LatenessCounting::BusinessDays;
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.amend_clause(clause_id(), terms), Ok(()));
+            assert_eq!(contract.add_holiday(date(2024, 1, 1)), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            contract
+        }
+
+        #[ink::test]
+        fn business_days_skip_weekends_and_holidays() {
+            let mut contract = business_day_contract();
+            // Agreed on Sunday 2023-12-31, delivered on Wednesday 2024-01-03 at noon
+            deliver_at(&mut contract, AGREED_DELIVERY + 3 * DAY + DAY / 2);
+
+            let preview = contract.preview_penalty(clause_id(), request()).unwrap();
+            assert_eq!(preview.breakdown.lateness_counting, LatenessCounting::BusinessDays);
+            assert_eq!(preview.breakdown.rounded_delay, 2 * DAY);
+            assert_eq!(preview.response.penalty, whole(200000));
+
+            // The same delivery counted in elapsed time is four days late
+            let accounts = ink::env::test::default_accounts::<Env>();
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.amend_clause(clause_id(), test_params_terms()), Ok(()));
+            let preview = contract.preview_penalty(clause_id(), request()).unwrap();
+            assert_eq!(preview.breakdown.rounded_delay, 4 * DAY);
+            assert_eq!(preview.response.penalty, whole(400000));
+        }
+
+        #[ink::test]
+        fn business_days_span_a_month_of_weeks() {
+            let mut contract = business_day_contract();
+            // Delivered one second into Thursday 2024-02-01, 33 calendar days late
+            deliver_at(&mut contract, AGREED_DELIVERY + 32 * DAY + SECOND);
+
+            let preview = contract.preview_penalty(clause_id(), request()).unwrap();
+            assert_eq!(preview.breakdown.rounded_delay, 23 * DAY);
+            assert!(preview.response.buyer_may_terminate);
+            assert_eq!(preview.response.penalty, whole(550000));
+        }
+
+        #[ink::test]
+        fn field_changes_are_stored_in_audit_log() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            let log_count = contract.get_audit_log_count();
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.set_attestor(Some(accounts.django)), Ok(()));
+            let mut terms = test_params_terms();
+            terms.cap_percentage = whole(60);
+            terms.termination = days(21);
+            assert_eq!(contract.amend_clause(clause_id(), terms), Ok(()));
+
+            let entries = contract.get_audit_log(log_count, 10);
+            assert_eq!(entries.len(), 2);
+            assert!(matches!(
+                &entries[0],
+                AuditLogEntry::DirectFieldChange { field_name, changed_by, clause_id: None, .. }
+                    if field_name == "attestor" && *changed_by == accounts.alice
+            ));
+            match &entries[1] {
+                AuditLogEntry::FunctionCall {
+                    function_name,
+                    clause_id: entry_clause_id,
+                    field_changes,
+                    ..
+                } => {
+                    assert_eq!(function_name, "amend_clause");
+                    assert_eq!(entry_clause_id, &Some(clause_id()));
+                    assert_eq!(
+                        field_changes,
+                        &vec![
+                            FieldChange {
+                                field_name: "cap_percentage".to_string(),
+                                old_value: "55".to_string(),
+                                new_value: "60".to_string(),
+                            },
+                            FieldChange {
+                                field_name: "termination".to_string(),
+                                old_value: days(14).to_string(),
+                                new_value: days(21).to_string(),
+                            },
+                        ]
+                    );
+                }
+                entry => panic!("unexpected audit log entry {:?}", entry),
+            }
+
+            assert_eq!(contract.get_audit_log_field_changes(0, 10), entries);
+            assert_eq!(
+                contract.get_audit_log_field_changes_by_field("termination".to_string()),
+                vec![entries[1].clone()]
+            );
+            assert!(contract
+                .get_audit_log_function_calls(0, 10)
+                .iter()
+                .all(|entry| matches!(entry, AuditLogEntry::FunctionCall { .. })));
+        }
+
+        #[ink::test]
+        fn audit_log_is_hash_chained() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+            deliver_at(&mut contract, AGREED_DELIVERY + SECOND);
+            contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            let count = contract.get_audit_log_count();
+            assert!(count >= 3);
+
+            let mut hash = Hash::default();
+            for (i, entry) in contract.get_audit_log(0, count).iter().enumerate() {
+                hash = LateDeliveryAndPenalty::chain_audit_hash(&hash, entry);
+                assert_eq!(contract.get_audit_log_hash(i as u64), Some(hash));
+            }
+            assert_eq!(contract.get_audit_log_head(), hash);
+            assert!(contract.verify_audit_range(0, count));
+            assert!(contract.verify_audit_range(count, count));
+            assert!(!contract.verify_audit_range(0, count + 1));
+            assert!(!contract.verify_audit_range(2, 1));
+
+            // Rewriting a stored entry breaks the chain from that entry onwards
+            let mut tampered = contract.get_audit_log(1, 1).remove(0);
+            if let AuditLogEntry::FunctionCall { ref mut caller, .. } = tampered {
+                *caller = accounts.eve;
+            }
+            contract.audit_log.insert(1, &tampered);
+            assert!(!contract.verify_audit_range(0, count));
+            assert!(!contract.verify_audit_range(1, count));
+            assert!(contract.verify_audit_range(2, count));
+        }
+
+        #[ink::test]
+        fn audited_calls_in_one_block_get_distinct_request_ids() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = bonded_contract(1_000_000);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.record_dispatch(clause_id()), Ok(()));
+            deliver_at(&mut contract, AGREED_DELIVERY + SECOND);
+            contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.acknowledge_penalty_assessment(0), Ok(()));
+
+            let request_ids: Vec<u64> = contract
+                .get_audit_log(0, contract.get_audit_log_count())
+                .iter()
+                .filter_map(|entry| match entry {
+                    AuditLogEntry::FunctionCall { request_id, .. } => Some(*request_id),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(request_ids, vec![0, 1, 2, 3, 4]);
+            assert_eq!(contract.get_request_count(), 5);
+        }
+
+        #[ink::test]
+        fn audit_log_is_queried_by_caller_time_and_function() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            let start = contract.get_audit_log_count();
+            let at = |caller, timestamp| {
+                ink::env::test::set_caller::<Env>(caller);
+                ink::env::test::set_block_timestamp::<Env>(timestamp);
+            };
+
+            at(accounts.alice, 1000);
+            assert_eq!(contract.set_attestor(Some(accounts.django)), Ok(()));
+            at(accounts.bob, 2000);
+            assert_eq!(contract.record_dispatch(clause_id()), Ok(()));
+            at(accounts.alice, 3000);
+            assert_eq!(contract.set_attestor(None), Ok(()));
+            at(accounts.charlie, 4000);
+            assert_eq!(contract.confirm_receipt(clause_id()), Ok(()));
+            at(accounts.alice, 5000);
+            assert_eq!(contract.set_attestor(Some(accounts.eve)), Ok(()));
+
+            let indexes = |page: &AuditLogPage| -> Vec<u64> {
+                page.entries.iter().map(|(index, _)| *index - start).collect()
+            };
+
+            let first = contract.get_audit_log_by_caller(accounts.alice, 0, u64::MAX, None, 2);
+            assert_eq!(indexes(&first), vec![0, 2]);
+            assert_eq!(first.next_cursor, Some(2));
+            let second =
+                contract.get_audit_log_by_caller(accounts.alice, 0, u64::MAX, first.next_cursor, 2);
+            assert_eq!(indexes(&second), vec![4]);
+            assert_eq!(second.next_cursor, None);
+
+            // Everything alice did from 2000 up to, but not including, 5000
+            let page = contract.get_audit_log_by_caller(accounts.alice, 2000, 5000, None, 10);
+            assert_eq!(indexes(&page), vec![2]);
+            assert_eq!(page.entries[0].1.timestamp(), 3000);
+
+            let page = contract.get_audit_log_by_time(2000, 4001, None, 10);
+            assert_eq!(indexes(&page), vec![1, 2, 3]);
+            assert!(contract.get_audit_log_by_time(6000, 7000, None, 10).entries.is_empty());
+
+            let page =
+                contract.get_audit_log_by_function("confirm_receipt".to_string(), 0, u64::MAX, None, 10);
+            assert_eq!(indexes(&page), vec![3]);
+            assert_eq!(page.entries[0].1.caller(), accounts.charlie);
+        }
+
+        /// Sibling hashes for the leaf at `position`, in a tree built like `audit_merkle_root`
+        fn merkle_proof(mut level: Vec<Hash>, mut position: usize) -> Vec<Hash> {
+            let mut proof = Vec::new();
+            while level.len() > 1 {
+                if let Some(sibling) = level.get(position ^ 1) {
+                    proof.push(*sibling);
+                }
+                level = level
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [left, right] => LateDeliveryAndPenalty::audit_node_hash(left, right),
+                        _ => pair[0],
+                    })
+                    .collect();
+                position /= 2;
+            }
+            proof
+        }
+
+        #[ink::test]
+        fn audit_log_is_archived_under_retention_policy() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            let attestors = [
+                Some(accounts.django),
+                None,
+                Some(accounts.eve),
+                None,
+                Some(accounts.frank),
+            ];
+            for (i, attestor) in attestors.into_iter().enumerate() {
+                ink::env::test::set_block_timestamp::<Env>(1000 * (i as u64 + 1));
+                assert_eq!(contract.set_attestor(attestor), Ok(()));
+            }
+            let entries = contract.get_audit_log(0, 10);
+            assert_eq!(entries.len(), 5);
+
+            // Nothing is archived until a policy allows it
+            assert_eq!(contract.archive_audit_log(10), Ok(0));
+            let keep_three = AuditRetention {
+                max_age: None,
+                max_entries: Some(3),
+            };
+            assert_eq!(contract.set_audit_retention(keep_three), Ok(()));
+            assert_eq!(contract.archive_audit_log(10), Ok(3));
+
+            assert_eq!(contract.get_audit_log_start(), 3);
+            assert_eq!(contract.get_audit_log(0, 10).len(), 3);
+            assert_eq!(contract.get_audit_log_hash(2), None);
+            assert!(contract.verify_audit_range(3, 6));
+            assert!(!contract.verify_audit_range(2, 6));
+
+            let checkpoint = contract.get_audit_checkpoint(0).unwrap();
+            assert_eq!((checkpoint.start, checkpoint.end), (0, 3));
+            let leaves: Vec<Hash> = entries[..3]
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| LateDeliveryAndPenalty::audit_leaf_hash(i as u64, entry))
+                .collect();
+            for (i, entry) in entries[..3].iter().enumerate() {
+                let proof = merkle_proof(leaves.clone(), i);
+                assert!(contract.verify_audit_proof(0, i as u64, entry.clone(), proof.clone()));
+                assert!(!contract.verify_audit_proof(0, i as u64, entries[3].clone(), proof));
+            }
+
+            // Pruned entries drop out of the indexes
+            let page = contract.get_audit_log_by_caller(accounts.alice, 0, u64::MAX, None, 10);
+            assert_eq!(page.entries.first().map(|(index, _)| *index), Some(3));
+
+            // Only the entry from 4000 is more than 5.5 seconds old at 10000
+            ink::env::test::set_block_timestamp::<Env>(10_000);
+            let max_age = AuditRetention {
+                max_age: Some(5_500),
+                max_entries: None,
+            };
+            assert_eq!(contract.set_audit_retention(max_age), Ok(()));
+            assert_eq!(contract.archive_audit_log(10), Ok(1));
+            let checkpoint = contract.get_audit_checkpoint(1).unwrap();
+            assert_eq!((checkpoint.start, checkpoint.end), (3, 4));
+            assert!(contract.verify_audit_range(4, contract.get_audit_log_count()));
+        }
+
+        #[ink::test]
+        fn draft_request_carries_concerto_template_data() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = clause(
+                true,
+                days(3),
+                Decimal::from_parts(125, 1).unwrap(),
+                whole(60),
+                days(20),
+                TemporalUnit::Days,
+            );
+            // Single line form from `archives/latedeliveryandpenalty/test-template-data.json`
+            let expected = concat!(
+                r#"{"$class":"io.clause.latedeliveryandpenalty@0.1.0.LateDeliveryAndPenalty","#,
+                r#""clauseId":"late-delivery-clause-1","forceMajeure":true,"#,
+                r#""penaltyDuration":{"$class":"org.accordproject.time@0.3.0.Duration","amount":3,"unit":"days"},"#,
+                r#""penaltyPercentage":12.5,"capPercentage":60,"#,
+                r#""termination":{"$class":"org.accordproject.time@0.3.0.Duration","amount":20,"unit":"days"},"#,
+                r#""fractionalPart":"days"}"#
+            );
+            assert_eq!(contract.get_template_data(clause_id()), Some(expected.to_string()));
+            assert_eq!(json_string("a \"b\"\n"), r#""a \"b\"\n""#);
+
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(
+                contract.request_draft(clause_id(), DraftFormat::Markdown),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(
+                contract.request_draft("po-2".to_string(), DraftFormat::Markdown),
+                Err(ContractError::InvalidInput)
+            );
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY);
+            assert_eq!(contract.request_draft(clause_id(), DraftFormat::Markdown), Ok(0));
+            assert_eq!(contract.request_draft(clause_id(), DraftFormat::Pdf), Ok(1));
+
+            let event = ink::env::test::recorded_events()
+                .filter_map(|event| <DraftRequested as scale::Decode>::decode(&mut &event.data[..]).ok())
+                .last()
+                .unwrap();
+            assert_eq!(event.requester, accounts.bob);
+            assert_eq!(event.request_id, 1);
+            assert_eq!(event.template_data, expected);
+            assert_eq!(event.timestamp, AGREED_DELIVERY);
+            assert_eq!(event.output_format, DraftFormat::Pdf);
+            assert_eq!(event.template_class, TEMPLATE_CLASS);
+            assert_eq!(event.namespace, TEMPLATE_NAMESPACE);
+        }
+
+        #[ink::test]
+        fn documents_stop_verifying_once_terms_change() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            let first = Hash::from([1; 32]);
+            let second = Hash::from([2; 32]);
+            assert!(!contract.verify_document(first));
+
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(
+                contract.register_document(clause_id(), first, DocumentHashAlgorithm::Sha2x256),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(
+                contract.register_document("po-2".to_string(), first, DocumentHashAlgorithm::Sha2x256),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(
+                contract.register_document(clause_id(), first, DocumentHashAlgorithm::Sha2x256),
+                Ok(1)
+            );
+            assert_eq!(
+                contract.register_document(clause_id(), first, DocumentHashAlgorithm::Sha2x256),
+                Err(ContractError::InvalidInput)
+            );
+            assert!(contract.verify_document(first));
+
+            let record = contract.get_document(first).unwrap();
+            assert_eq!(record.version, 1);
+            assert_eq!(record.submitter, accounts.bob);
+            assert_eq!(
+                record.template_data_hash,
+                LateDeliveryAndPenalty::blake2x256(
+                    contract.get_template_data(clause_id()).unwrap().as_bytes()
+                )
+            );
+
+            // Amending the terms leaves the agreed document out of date
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            let mut terms = test_params_terms();
+            terms.cap_percentage = whole(60);
+            assert_eq!(contract.amend_clause(clause_id(), terms), Ok(()));
+            assert!(!contract.verify_document(first));
+            let changes = contract.get_audit_log_field_changes_by_field("document".to_string());
+            assert_eq!(changes.len(), 1);
+
+            assert_eq!(
+                contract.register_document(clause_id(), second, DocumentHashAlgorithm::Blake2x256),
+                Ok(2)
+            );
+            assert!(contract.verify_document(second));
+            assert_eq!(contract.get_latest_document(clause_id()).unwrap().document_hash, second);
+        }
+
+        #[ink::test]
+        fn arbitrator_resolves_disputes_while_claims_are_frozen() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let evidence = Hash::from([9; 32]);
+            let mut contract = test_params_contract(true);
+            deliver_at(&mut contract, AGREED_DELIVERY + 3 * DAY);
+            assert_eq!(contract.get_arbitrator(), accounts.django);
+
+            // Only the buyer and seller open disputes, and only about something that exists
+            let subject = DisputeSubject::Penalty {
+                clause_id: clause_id(),
+            };
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(
+                contract.open_dispute(subject.clone(), evidence),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let unknown = DisputeSubject::ForceMajeureClaim { claim_id: 0 };
+            assert_eq!(contract.open_dispute(unknown, evidence), Err(ContractError::InvalidInput));
+            let penalty_dispute = contract.open_dispute(subject.clone(), evidence).unwrap();
+            assert_eq!(
+                contract.open_dispute(subject, evidence),
+                Err(ContractError::InvalidInput)
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(
+                contract.late_delivery_and_penalty(clause_id(), request()),
+                Err(ContractError::DisputeOpen)
+            );
+
+            // The outcome must fit the subject, and only the arbitrator can decide
+            let award = DisputeOutcome::PenaltyOverride {
+                penalty: whole(150000),
+            };
+            assert_eq!(
+                contract.resolve_dispute(penalty_dispute, award.clone()),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.django);
+            let ruling = DisputeOutcome::ForceMajeureRuling { accepted: true };
+            assert_eq!(
+                contract.resolve_dispute(penalty_dispute, ruling.clone()),
+                Err(ContractError::InvalidInput)
+            );
+            assert_eq!(contract.resolve_dispute(penalty_dispute, award.clone()), Ok(()));
+            assert_eq!(
+                contract.resolve_dispute(penalty_dispute, DisputeOutcome::Dismissed),
+                Err(ContractError::InvalidInput)
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let response = contract.late_delivery_and_penalty(clause_id(), request()).unwrap();
+            assert_eq!(response.penalty, whole(150000));
+            assert_eq!(contract.get_penalty_override(clause_id()), Some(whole(150000)));
+
+            // A disputed force majeure claim can be taken to the arbitrator, who may accept it
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let window = (AGREED_DELIVERY, AGREED_DELIVERY + DAY);
+            let category = ForceMajeureCategory::GovernmentAction;
+            let claim_id = contract
+                .file_force_majeure_claim(clause_id(), category, window.0, window.1, evidence)
+                .unwrap();
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.dispute_force_majeure_claim(claim_id), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let claim_dispute = contract
+                .open_dispute(DisputeSubject::ForceMajeureClaim { claim_id }, evidence)
+                .unwrap();
+            assert_eq!(contract.release_bond(clause_id()), Err(ContractError::DisputeOpen));
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.release_bond(clause_id()), Err(ContractError::DisputeOpen));
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(contract.resolve_dispute(claim_dispute, ruling), Ok(()));
+            let claim = contract.get_force_majeure_claim(claim_id).unwrap();
+            assert_eq!(claim.status, ForceMajeureStatus::Accepted);
+
+            assert!(contract.get_open_disputes().is_empty());
+            let history = contract.get_disputes(0, 10);
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[0].outcome, Some(award));
+            assert_eq!(history[1].opened_by, accounts.bob);
+            let function_name = "resolve_dispute".to_string();
+            let resolved = contract.get_audit_log_by_function(function_name, 0, u64::MAX, None, 10);
+            assert_eq!(resolved.entries.len(), 2);
+        }
+
+        #[ink::test]
+        fn resolved_disputes_cool_down_before_reopening() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let evidence = Hash::from([9; 32]);
+            let mut contract = test_params_contract(true);
+            let resolved_at = AGREED_DELIVERY + 3 * DAY;
+            deliver_at(&mut contract, resolved_at);
+            let subject = DisputeSubject::Penalty {
+                clause_id: clause_id(),
+            };
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let dismissed = contract.open_dispute(subject.clone(), evidence).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(contract.resolve_dispute(dismissed, DisputeOutcome::Dismissed), Ok(()));
+
+            // Neither party can freeze the clause again straight away, so claims go through
+            for party in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<Env>(party);
+                assert_eq!(
+                    contract.open_dispute(subject.clone(), evidence),
+                    Err(ContractError::InvalidInput)
+                );
+            }
+            ink::env::test::set_block_timestamp::<Env>(resolved_at + DISPUTE_COOLDOWN - 1);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+
+            ink::env::test::set_block_timestamp::<Env>(resolved_at + DISPUTE_COOLDOWN);
+            assert_eq!(contract.open_dispute(subject, evidence), Ok(dismissed + 1));
+        }
+
+        #[ink::test]
+        fn unbonded_penalties_are_settled_in_instalments() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = test_params_contract(false);
+            let buyer_balance = balance_of(accounts.charlie);
+
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + SECOND);
+            assert!(contract.late_delivery_and_penalty(clause_id(), request()).is_ok());
+            let assessment = contract.get_penalty_assessment(0).unwrap();
+            assert_eq!((assessment.amount, assessment.paid), (100_000, 0));
+            assert_eq!(assessment.due_at, AGREED_DELIVERY + SECOND + 30 * DAY);
+
+            // Only the seller acknowledges and pays, and only once
+            assert_eq!(
+                contract.acknowledge_penalty_assessment(0),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.acknowledge_penalty_assessment(0), Ok(()));
+            assert_eq!(
+                contract.acknowledge_penalty_assessment(0),
+                Err(ContractError::InvalidInput)
+            );
+
+            ink::env::test::transfer_in::<Env>(40_000);
+            assert_eq!(contract.pay_penalty_assessment(0), Ok(()));
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + 40_000);
+            ink::env::test::transfer_in::<Env>(60_001);
+            assert_eq!(contract.pay_penalty_assessment(0), Err(ContractError::InvalidInput));
+            ink::env::test::set_value_transferred::<Env>(0);
+
+            // The buyer waives part of the rest, and whatever remains falls overdue
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.write_off_penalty_assessment(0, 10_000), Ok(()));
+            assert!(contract.get_overdue_penalty_assessments(0, 10).is_empty());
+            ink::env::test::set_block_timestamp::<Env>(AGREED_DELIVERY + SECOND + 31 * DAY);
+            assert!(contract.is_penalty_assessment_overdue(0));
+            assert_eq!(contract.get_overdue_penalty_assessments(0, 10).len(), 1);
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            ink::env::test::transfer_in::<Env>(50_000);
+            assert_eq!(contract.pay_penalty_assessment(0), Ok(()));
+            ink::env::test::set_value_transferred::<Env>(0);
+            assert!(!contract.is_penalty_assessment_overdue(0));
+
+            let ledger = contract.get_penalty_ledger(clause_id());
+            assert_eq!(
+                ledger,
+                PenaltyLedger {
+                    assessed: 100_000,
+                    acknowledged: 100_000,
+                    paid: 90_000,
+                    written_off: 10_000,
+                    credited: 0,
+                    reduced: 0,
+                }
+            );
+            assert_eq!(contract.get_penalty_paid(clause_id()), 90_000);
+            assert_eq!(balance_of(accounts.charlie), buyer_balance + 90_000);
         }
     }
 }
@@ -1,10 +1,16 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+// Public so the generated model types are part of the crate's API instead of dead code
 #[ink::contract]
-mod propertysale {
+// The generated constructor dispatch enum is sized by `new`'s arguments
+#[allow(clippy::large_enum_variant)]
+pub mod propertysale {
     use ink::prelude::format;
     use ink::prelude::string::{String, ToString};
     use ink::prelude::vec::Vec;
+
+    pub use contract_common::{Decimal, DraftFormat, Rounding, DISPUTE_COOLDOWN, MAX_OPEN_DISPUTES};
+    use contract_common::Disputes;
     // Note: AccountId32 and Ss58Codec are not needed for no_std builds
 
     // Error types
@@ -15,10 +21,193 @@ mod propertysale {
         ContractPaused,
         InvalidInput,
         ProcessingFailed,
+        /// A request broke a business rule while the contract is in `FailureMode::Revert`
+        Rejected(SaleError),
+        /// The operation is frozen while a dispute about it awaits the arbitrator
+        DisputeOpen,
     }
 
     pub type Result<T> = core::result::Result<T, ContractError>;
 
+    /// Why an offer or signing request was rejected. Discriminants are part of the
+    /// encoded response and must not be renumbered.
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    #[repr(u8)]
+    pub enum SaleError {
+        /// Only buyers can submit or cancel offers
+        NotABuyer = 0,
+        /// Only sellers can accept or reject offers
+        NotASeller = 1,
+        /// Only buyers and sellers can sign the contract
+        NotAParty = 2,
+        /// Submit requires an offer amount
+        OfferAmountRequired = 3,
+        /// There is no offer to update or sign
+        NoOffer = 4,
+        /// The offer must be accepted before signing
+        OfferNotAccepted { status: OfferStatus } = 5,
+        /// The purchase price must be set before signing
+        PurchasePriceNotSet = 6,
+        /// The purchase price must equal the accepted offer, currency included
+        PurchasePriceMismatch { expected: Money, actual: Money } = 7,
+        /// The contract needs at least one seller before signing
+        NoSellers = 8,
+        /// The contract needs at least one buyer before signing
+        NoBuyers = 9,
+        /// The caller has already signed
+        AlreadySigned = 10,
+        /// The latest registered document no longer matches the contract data
+        DocumentOutdated = 11,
+    }
+
+    /// How `manage_offer` and `sign_contract` settle a request that breaks a business rule
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum FailureMode {
+        /// Commit the request and return `Ok` with `success: false` and the error
+        #[default]
+        Respond,
+        /// Return `Err(ContractError::Rejected)`, rolling back all state and events
+        Revert,
+    }
+
+    /// Upper bound on audit log entries archived into one checkpoint
+    pub const MAX_AUDIT_ARCHIVE_BATCH: u64 = 256;
+
+    /// Concerto namespace and `$class` of the template this contract was generated from
+    pub const TEMPLATE_NAMESPACE: &str = "realestatesaleuk@1.0.0";
+    pub const TEMPLATE_CLASS: &str = "realestatesaleuk@1.0.0.PropertySale";
+
+    // === CONCERTO JSON ===
+
+    /// Quote and escape a string as a JSON string literal
+    fn json_string(value: &str) -> String {
+        let mut quoted = String::from("\"");
+        for c in value.chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    /// `0x` prefixed lowercase hex, the form wallet addresses take in template data
+    fn hex_string(bytes: &[u8]) -> String {
+        let mut hex = String::from("0x");
+        for byte in bytes {
+            hex.push_str(&format!("{:02x}", byte));
+        }
+        hex
+    }
+
+    /// Concerto DateTime for a Unix millisecond timestamp, e.g. `2024-01-15T10:00:00.000Z`.
+    ///
+    /// Uses the days-to-civil algorithm from <https://howardhinnant.github.io/date_algorithms.html>.
+    fn json_date_time(timestamp: u64) -> String {
+        let days = timestamp / 86_400_000;
+        let millis = timestamp % 86_400_000;
+
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let day_of_era = z % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+        format!(
+            "\"{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z\"",
+            year,
+            month,
+            day,
+            millis / 3_600_000,
+            millis / 60_000 % 60,
+            millis / 1_000 % 60,
+            millis % 1_000
+        )
+    }
+
+    impl Money {
+        fn to_template_json(&self) -> String {
+            format!(
+                "{{\"$class\":\"{}.Money\",\"amount\":{},\"currencyCode\":\"{:?}\"}}",
+                TEMPLATE_NAMESPACE, self.amount, self.currency_code
+            )
+        }
+    }
+
+    impl Offer {
+        fn to_template_json(&self) -> String {
+            format!(
+                "{{\"$class\":\"{}.Offer\",\"offer\":{},\"offerStatus\":\"{:?}\",\"offerDate\":{}}}",
+                TEMPLATE_NAMESPACE,
+                self.offer.to_template_json(),
+                self.offer_status,
+                json_date_time(self.offer_date)
+            )
+        }
+    }
+
+    impl PropertyAddress {
+        fn to_template_json(&self) -> String {
+            format!(
+                "{{\"$class\":\"{}.Address\",\"addressLine1\":{},\"addressLine2\":{},\"city\":{},\
+                 \"postCode\":{},\"county\":{},\"country\":\"{:?}\"}}",
+                TEMPLATE_NAMESPACE,
+                json_string(&self.address_line1),
+                json_string(&self.address_line2),
+                json_string(&self.city),
+                json_string(&self.post_code),
+                json_string(&self.county),
+                self.country
+            )
+        }
+    }
+
+    impl Party {
+        fn to_template_json(&self, signing_state: bool) -> String {
+            let signed_at = match self.signed_at {
+                Some(signed_at) if signing_state => {
+                    format!(",\"signedAt\":{}", json_date_time(signed_at))
+                }
+                _ => String::new(),
+            };
+            format!(
+                "{{\"$class\":\"{}.Party\",\"partyId\":{},\"fullName\":{},\"email\":{},\"mobile\":{},\
+                 \"address\":{},\"walletAddress\":\"{}\"{}}}",
+                TEMPLATE_NAMESPACE,
+                json_string(&self.party_id),
+                json_string(&self.full_name),
+                json_string(&self.email),
+                json_string(&self.mobile),
+                self.address.to_template_json(),
+                hex_string(self.wallet_address.as_ref()),
+                signed_at
+            )
+        }
+    }
+
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
     #[cfg_attr(
         feature = "std",
@@ -43,7 +232,7 @@ mod propertysale {
     )]
     pub struct ManageOfferResponse {
         pub success: bool,
-        pub error_message: Option<String>,
+        pub error: Option<SaleError>,
     }
 
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
@@ -53,7 +242,7 @@ mod propertysale {
     )]
     pub struct SignContractResponse {
         pub success: bool,
-        pub error_message: Option<String>,
+        pub error: Option<SaleError>,
     }
 
     #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
@@ -71,7 +260,7 @@ mod propertysale {
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     pub struct Money {
-        pub amount: u128,
+        pub amount: Decimal,
         pub currency_code: CurrencyCode,
     }
 
@@ -136,6 +325,7 @@ mod propertysale {
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
+    #[allow(clippy::upper_case_acronyms)]
     pub enum CurrencyCode {
         #[default]
         EUR,
@@ -148,6 +338,7 @@ mod propertysale {
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
+    #[allow(clippy::upper_case_acronyms)]
     pub enum Country {
         #[default]
         UK,
@@ -219,13 +410,177 @@ mod propertysale {
         } = 1,
     }
 
+    impl AuditLogEntry {
+        /// Account that made the call or the change
+        pub fn caller(&self) -> AccountId {
+            match self {
+                AuditLogEntry::FunctionCall { caller, .. } => *caller,
+                AuditLogEntry::DirectFieldChange { changed_by, .. } => *changed_by,
+            }
+        }
+
+        pub fn timestamp(&self) -> u64 {
+            match self {
+                AuditLogEntry::FunctionCall { timestamp, .. }
+                | AuditLogEntry::DirectFieldChange { timestamp, .. } => *timestamp,
+            }
+        }
+    }
+
+    /// A page of audit log entries with their log indexes, and the cursor for the next page
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct AuditLogPage {
+        pub entries: Vec<(u64, AuditLogEntry)>,
+        pub next_cursor: Option<u64>,
+    }
+
+    /// Which audit log entries may be archived and pruned; by default nothing is
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct AuditRetention {
+        /// Entries older than this many milliseconds
+        pub max_age: Option<u64>,
+        /// Entries beyond this many of the most recent
+        pub max_entries: Option<u64>,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum DocumentHashAlgorithm {
+        #[default]
+        Blake2x256,
+        Sha2x256,
+    }
+
+    /// A rendered agreement document, identified by the hash of its text
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct DocumentRecord {
+        /// Starts at 1
+        pub version: u32,
+        pub document_hash: Hash,
+        pub algorithm: DocumentHashAlgorithm,
+        /// Blake2x256 of the template data the document was rendered from, without signing
+        /// state
+        pub template_data_hash: Hash,
+        pub submitter: AccountId,
+        pub timestamp: u64,
+    }
+
+    /// Merkle root over archived entries `start..end`, kept after the entries are pruned
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct AuditCheckpoint {
+        pub start: u64,
+        pub end: u64,
+        pub root: Hash,
+        /// Hash chain value of the last archived entry
+        pub head: Hash,
+        pub archived_by: AccountId,
+        pub archived_at: u64,
+    }
+
+    /// A processed request and the response it produced, keyed by request id
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    #[repr(u8)]
+    pub enum RequestRecord {
+        ManageOffer {
+            request_id: u64,
+            submitter: AccountId,
+            timestamp: u64,
+            request: ManageOfferRequest,
+            response: ManageOfferResponse,
+        } = 0,
+        SignContract {
+            request_id: u64,
+            submitter: AccountId,
+            timestamp: u64,
+            request: SignContractRequest,
+            response: SignContractResponse,
+        } = 1,
+    }
+
+    /// What the buyers and sellers disagree about
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum DisputeSubject {
+        /// The current offer; freezes offer changes and signing
+        Offer,
+        /// A party's signature; freezes signing
+        Signature { party_id: String },
+    }
+
+    /// The arbitrator's binding decision
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum DisputeOutcome {
+        /// Nothing changes
+        Dismissed,
+        /// The offer goes back to pending and every signature made on it is voided
+        RevertOffer,
+        /// The disputed party's signature is voided
+        VoidSignature,
+    }
+
+    pub type Dispute = contract_common::Dispute<DisputeSubject, DisputeOutcome>;
+
     #[ink(storage)]
     pub struct PropertySale {
         owner: AccountId,
+        arbitrator: AccountId,
         paused: bool,
+        failure_mode: FailureMode,
         audit_log: ink::storage::Mapping<u64, AuditLogEntry>,
         audit_log_count: u64,
+        /// Running hash of each audit log entry, chained from the previous entry's hash
+        audit_log_hashes: ink::storage::Mapping<u64, Hash>,
+        audit_log_head: Hash,
+        /// Log indexes of each caller's entries, by position in that caller's history
+        audit_log_by_caller: ink::storage::Mapping<(AccountId, u64), u64>,
+        audit_log_caller_count: ink::storage::Mapping<AccountId, u64>,
+        audit_log_caller_start: ink::storage::Mapping<AccountId, u64>,
+        /// Log indexes of function call entries, by position in that function's history
+        audit_log_by_function: ink::storage::Mapping<(String, u64), u64>,
+        audit_log_function_count: ink::storage::Mapping<String, u64>,
+        audit_log_function_start: ink::storage::Mapping<String, u64>,
+        /// First entry not yet archived and pruned
+        audit_log_start: u64,
+        /// Hash chain value of the last pruned entry
+        audit_archived_head: Hash,
+        audit_retention: AuditRetention,
+        audit_checkpoints: ink::storage::Mapping<u64, AuditCheckpoint>,
+        audit_checkpoint_count: u64,
         pending_field_changes: Vec<FieldChange>,
+        requests: ink::storage::Mapping<u64, RequestRecord>,
+        request_count: u64,
+        documents: ink::storage::Mapping<Hash, DocumentRecord>,
+        /// Document hash of each version of the agreement
+        document_versions: ink::storage::Mapping<u32, Hash>,
+        document_count: u32,
+        disputes: Disputes<DisputeSubject, DisputeOutcome>,
         sellers: Vec<Party>,
         buyers: Vec<Party>,
         property_address: PropertyAddress,
@@ -237,6 +592,25 @@ mod propertysale {
         status: ContractStatus,
     }
 
+    #[ink(event)]
+    pub struct DisputeOpened {
+        #[ink(topic)]
+        pub dispute_id: u64,
+        #[ink(topic)]
+        pub opened_by: AccountId,
+        pub subject: DisputeSubject,
+        pub evidence_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        pub dispute_id: u64,
+        #[ink(topic)]
+        pub arbitrator: AccountId,
+        pub outcome: DisputeOutcome,
+    }
+
     #[ink(event)]
     pub struct ContractCreated {
         #[ink(topic)]
@@ -255,6 +629,30 @@ mod propertysale {
         pub by: AccountId,
     }
 
+    /// Asks the draft service to render the template. LateDeliveryAndPenalty emits the same
+    /// fields, and the service decodes them with the contract's ABI.
+    #[ink(event)]
+    pub struct DraftRequested {
+        #[ink(topic)]
+        pub requester: AccountId,
+        pub request_id: u64,
+        /// Contract data in Concerto JSON, as in `test-template-data.json`
+        pub template_data: String,
+        pub timestamp: u64,
+        pub output_format: DraftFormat,
+        pub template_class: String,
+        pub namespace: String,
+    }
+
+    #[ink(event)]
+    pub struct DocumentRegistered {
+        #[ink(topic)]
+        pub document_hash: Hash,
+        pub version: u32,
+        pub template_data_hash: Hash,
+        pub submitter: AccountId,
+    }
+
     #[ink(event)]
     pub struct ManageOfferRequestSubmitted {
         #[ink(topic)]
@@ -304,6 +702,15 @@ mod propertysale {
         pub timestamp: u64,
     }
 
+    #[ink(event)]
+    pub struct AuditLogArchived {
+        #[ink(topic)]
+        pub checkpoint_id: u64,
+        pub start: u64,
+        pub end: u64,
+        pub root: Hash,
+    }
+
     #[ink(event)]
     pub struct ContractDataChanged {
         #[ink(topic)]
@@ -343,55 +750,41 @@ mod propertysale {
         }
 
         /// Helper function to validate contract is ready for signing
-        fn validate_contract_ready_for_signing(&self) -> core::result::Result<(), String> {
+        fn validate_contract_ready_for_signing(&self) -> core::result::Result<(), SaleError> {
             // Check at least 1 seller
             if self.sellers.is_empty() {
-                return Err("Contract must have at least one seller before signing".to_string());
+                return Err(SaleError::NoSellers);
             }
 
             // Check at least 1 buyer
             if self.buyers.is_empty() {
-                return Err("Contract must have at least one buyer before signing".to_string());
+                return Err(SaleError::NoBuyers);
             }
 
             // Check offer exists and is accepted
-            match &self.offer {
-                Some(offer) => {
-                    if offer.offer_status != OfferStatus::Accepted {
-                        return Err(format!(
-                            "Offer must be accepted before signing. Current status: {:?}",
-                            offer.offer_status
-                        ));
-                    }
+            let offer = self.offer.as_ref().ok_or(SaleError::NoOffer)?;
+            if offer.offer_status != OfferStatus::Accepted {
+                return Err(SaleError::OfferNotAccepted {
+                    status: offer.offer_status.clone(),
+                });
+            }
 
-                    // Check purchase price exists and matches offer amount
-                    match &self.purchase_price {
-                        Some(purchase_price) => {
-                            if purchase_price.amount != offer.offer.amount {
-                                return Err(format!(
-                                    "Purchase price ({} {:?}) must equal offer amount ({} {:?})",
-                                    purchase_price.amount,
-                                    purchase_price.currency_code,
-                                    offer.offer.amount,
-                                    offer.offer.currency_code
-                                ));
-                            }
-                            if purchase_price.currency_code != offer.offer.currency_code {
-                                return Err(format!(
-                                    "Purchase price currency ({:?}) must match offer currency ({:?})",
-                                    purchase_price.currency_code, offer.offer.currency_code
-                                ));
-                            }
-                        }
-                        None => {
-                            return Err("Purchase price must be set before signing".to_string());
-                        }
-                    }
-                }
-                None => {
-                    return Err(
-                        "No offer exists to sign - an accepted offer is required".to_string()
-                    );
+            // Check purchase price exists and matches offer amount and currency
+            let purchase_price = self
+                .purchase_price
+                .as_ref()
+                .ok_or(SaleError::PurchasePriceNotSet)?;
+            if *purchase_price != offer.offer {
+                return Err(SaleError::PurchasePriceMismatch {
+                    expected: offer.offer.clone(),
+                    actual: purchase_price.clone(),
+                });
+            }
+
+            // Once a document is registered, parties sign it rather than changed data
+            if let Some(document) = self.get_latest_document() {
+                if document.template_data_hash != self.template_data_hash() {
+                    return Err(SaleError::DocumentOutdated);
                 }
             }
 
@@ -399,7 +792,10 @@ mod propertysale {
         }
 
         /// Helper function to find and update the signing party
-        fn find_and_sign_party(&mut self, caller: AccountId) -> Result<()> {
+        fn find_and_sign_party(
+            &mut self,
+            caller: AccountId,
+        ) -> core::result::Result<(), SaleError> {
             let current_timestamp = self.env().block_timestamp();
 
             // First, find the seller index (if any)
@@ -407,7 +803,7 @@ mod propertysale {
             for (index, seller) in self.sellers.iter().enumerate() {
                 if self.is_caller_matching_account(caller, seller.wallet_address) {
                     if seller.signed_at.is_some() {
-                        return Err(ContractError::InvalidInput);
+                        return Err(SaleError::AlreadySigned);
                     }
                     seller_index = Some(index);
                     break;
@@ -425,7 +821,7 @@ mod propertysale {
             for (index, buyer) in self.buyers.iter().enumerate() {
                 if self.is_caller_matching_account(caller, buyer.wallet_address) {
                     if buyer.signed_at.is_some() {
-                        return Err(ContractError::InvalidInput);
+                        return Err(SaleError::AlreadySigned);
                     }
                     buyer_index = Some(index);
                     break;
@@ -439,7 +835,7 @@ mod propertysale {
             }
 
             // Not found as either seller or buyer
-            Err(ContractError::Unauthorized)
+            Err(SaleError::NotAParty)
         }
 
         /// Helper function to check if all parties have signed
@@ -484,11 +880,12 @@ mod propertysale {
         fn filter_valid_parties(parties: Vec<Party>) -> Vec<Party> {
             parties
                 .into_iter()
-                .filter(|party| Self::is_valid_party(party))
+                .filter(Self::is_valid_party)
                 .collect()
         }
 
         #[ink(constructor)]
+        #[allow(clippy::too_many_arguments)]
         pub fn new(
             sellers: Vec<Party>,
             buyers: Vec<Party>,
@@ -499,6 +896,8 @@ mod propertysale {
             offer: Option<Offer>,
             agreement_date: Option<u64>,
             status: ContractStatus,
+            failure_mode: FailureMode,
+            arbitrator: AccountId,
         ) -> Self {
             let caller = Self::env().caller();
 
@@ -517,10 +916,31 @@ mod propertysale {
 
             Self {
                 owner: caller,
+                arbitrator,
                 paused: false,
+                failure_mode,
                 audit_log: ink::storage::Mapping::default(),
                 audit_log_count: 0,
+                audit_log_hashes: ink::storage::Mapping::default(),
+                audit_log_head: Hash::default(),
+                audit_log_by_caller: ink::storage::Mapping::default(),
+                audit_log_caller_count: ink::storage::Mapping::default(),
+                audit_log_caller_start: ink::storage::Mapping::default(),
+                audit_log_by_function: ink::storage::Mapping::default(),
+                audit_log_function_count: ink::storage::Mapping::default(),
+                audit_log_function_start: ink::storage::Mapping::default(),
+                audit_log_start: 0,
+                audit_archived_head: Hash::default(),
+                audit_retention: AuditRetention::default(),
+                audit_checkpoints: ink::storage::Mapping::default(),
+                audit_checkpoint_count: 0,
                 pending_field_changes: Vec::new(),
+                requests: ink::storage::Mapping::default(),
+                request_count: 0,
+                documents: ink::storage::Mapping::default(),
+                document_versions: ink::storage::Mapping::default(),
+                document_count: 0,
+                disputes: Disputes::default(),
                 sellers: valid_sellers,
                 buyers: valid_buyers,
                 property_address: valid_property_address,
@@ -534,6 +954,8 @@ mod propertysale {
         }
 
         #[ink(constructor)]
+        // A constructor, not `Default::default`
+        #[allow(clippy::should_implement_trait)]
         pub fn default() -> Self {
             Self::new(
                 Vec::new(),
@@ -545,6 +967,8 @@ mod propertysale {
                 None,
                 None,
                 ContractStatus::Draft,
+                FailureMode::default(),
+                Self::env().caller(),
             )
         }
 
@@ -553,6 +977,11 @@ mod propertysale {
             self.owner
         }
 
+        #[ink(message)]
+        pub fn get_arbitrator(&self) -> AccountId {
+            self.arbitrator
+        }
+
         #[ink(message)]
         pub fn is_paused(&self) -> bool {
             self.paused
@@ -582,6 +1011,38 @@ mod propertysale {
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn get_failure_mode(&self) -> FailureMode {
+            self.failure_mode
+        }
+
+        #[ink(message)]
+        pub fn set_failure_mode(&mut self, new_value: FailureMode) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let old_value = format!("{:?}", self.failure_mode);
+            let new_value_str = format!("{:?}", new_value);
+            self.log_direct_field_change("failure_mode", &old_value, &new_value_str);
+            self.failure_mode = new_value;
+            Ok(())
+        }
+
+        /// In revert mode, turn a rejected response into an error so the transaction
+        /// rolls back along with its request record, audit entries and events
+        fn settle_rejection(&self, error: &Option<SaleError>) -> Result<()> {
+            match (self.failure_mode, error) {
+                (FailureMode::Revert, Some(error)) => Err(ContractError::Rejected(error.clone())),
+                _ => Ok(()),
+            }
+        }
+
         // Compare caller's AccountId with stored address
         fn is_caller_matching_account(&self, caller: AccountId, stored_address: AccountId) -> bool {
             let match_result = caller == stored_address;
@@ -625,8 +1086,12 @@ mod propertysale {
                 return Err(ContractError::ContractPaused);
             }
 
+            if self.disputes.open_about(&DisputeSubject::Offer).is_some() {
+                return Err(ContractError::DisputeOpen);
+            }
+
             let caller = self.env().caller();
-            let request_id = self.env().block_number() as u64;
+            let request_id = self.next_request_id();
 
             self.env().emit_event(ManageOfferRequestSubmitted {
                 submitter: caller,
@@ -637,22 +1102,20 @@ mod propertysale {
             match _request.action {
                 OfferAction::Submit | OfferAction::Cancel => {
                     if !self.is_caller_buyer(caller) {
-                        return Ok(ManageOfferResponse {
+                        let response = ManageOfferResponse {
                             success: false,
-                            error_message: Some(
-                                "Only buyers can submit or cancel offers".to_string(),
-                            ),
-                        });
+                            error: Some(SaleError::NotABuyer),
+                        };
+                        return self.respond_manage_offer(request_id, &_request, response);
                     }
                 }
                 OfferAction::Accept | OfferAction::Reject => {
                     if !self.is_caller_seller(caller) {
-                        return Ok(ManageOfferResponse {
+                        let response = ManageOfferResponse {
                             success: false,
-                            error_message: Some(
-                                "Only sellers can accept or reject offers".to_string(),
-                            ),
-                        });
+                            error: Some(SaleError::NotASeller),
+                        };
+                        return self.respond_manage_offer(request_id, &_request, response);
                     }
                 }
             }
@@ -661,7 +1124,7 @@ mod propertysale {
             let response = match _request.action {
                 OfferAction::Submit => {
                     // For Submit action, we need a new offer amount
-                    match _request.offer {
+                    match _request.offer.clone() {
                         Some(offer_money) => {
                             // Create new offer with Pending status and current timestamp
                             let new_offer = Offer {
@@ -694,14 +1157,12 @@ mod propertysale {
 
                             ManageOfferResponse {
                                 success: true,
-                                error_message: None,
+                                error: None,
                             }
                         }
                         None => ManageOfferResponse {
                             success: false,
-                            error_message: Some(
-                                "Offer amount is required for Submit action".to_string(),
-                            ),
+                            error: Some(SaleError::OfferAmountRequired),
                         },
                     }
                 }
@@ -753,26 +1214,38 @@ mod propertysale {
 
                             ManageOfferResponse {
                                 success: true,
-                                error_message: None,
+                                error: None,
                             }
                         }
                         None => ManageOfferResponse {
                             success: false,
-                            error_message: Some("No existing offer to update".to_string()),
+                            error: Some(SaleError::NoOffer),
                         },
                     }
                 }
             };
             // === END CUSTOM LOGIC ===
 
+            self.respond_manage_offer(request_id, &_request, response)
+        }
+
+        /// Store, log and announce a manage_offer response, then apply the failure mode to it
+        fn respond_manage_offer(
+            &mut self,
+            request_id: u64,
+            request: &ManageOfferRequest,
+            response: ManageOfferResponse,
+        ) -> Result<ManageOfferResponse> {
             // Log function call for audit trail
+            self.store_manage_offer(request_id, request, &response);
             self.log_function_call("manage_offer", request_id);
 
             self.env().emit_event(ManageOfferResponseGenerated {
                 request_id,
-                success: true,
+                success: response.success,
             });
 
+            self.settle_rejection(&response.error)?;
             Ok(response)
         }
 
@@ -785,7 +1258,11 @@ mod propertysale {
                 return Err(ContractError::ContractPaused);
             }
 
-            let request_id = self.env().block_number() as u64;
+            if self.disputes.has_open() {
+                return Err(ContractError::DisputeOpen);
+            }
+
+            let request_id = self.next_request_id();
 
             self.env().emit_event(SignContractRequestSubmitted {
                 submitter: self.env().caller(),
@@ -796,11 +1273,12 @@ mod propertysale {
             let caller = self.env().caller();
 
             // Validate contract is ready for signing
-            if let Err(error_msg) = self.validate_contract_ready_for_signing() {
-                return Ok(SignContractResponse {
+            if let Err(error) = self.validate_contract_ready_for_signing() {
+                let response = SignContractResponse {
                     success: false,
-                    error_message: Some(error_msg),
-                });
+                    error: Some(error),
+                };
+                return self.respond_sign_contract(request_id, &_request, response);
             }
 
             // Check if this is the first signature (before any changes)
@@ -809,6 +1287,12 @@ mod propertysale {
             // Find and sign the party
             let response = match self.find_and_sign_party(caller) {
                 Ok(_) => {
+                    // Record which agreed document was signed
+                    if let Some(document) = self.get_latest_document() {
+                        let document_value = hex_string(document.document_hash.as_ref());
+                        self.log_direct_field_change("signed_document", "", &document_value);
+                    }
+
                     // Log the sellers change
                     let sellers_value = format!("{:?}", self.sellers);
                     self.log_direct_field_change("sellers", "sellers_updated", &sellers_value);
@@ -842,28 +1326,28 @@ mod propertysale {
 
                     SignContractResponse {
                         success: true,
-                        error_message: None,
-                    }
-                }
-                Err(contract_error) => {
-                    let error_msg = match contract_error {
-                        ContractError::Unauthorized => {
-                            "Only buyers and sellers can sign the contract".to_string()
-                        }
-                        ContractError::InvalidInput => {
-                            "Party has already signed or invalid signing attempt".to_string()
-                        }
-                        _ => "Signing failed".to_string(),
-                    };
-                    SignContractResponse {
-                        success: false,
-                        error_message: Some(error_msg),
+                        error: None,
                     }
                 }
+                Err(error) => SignContractResponse {
+                    success: false,
+                    error: Some(error),
+                },
             };
             // === END CUSTOM LOGIC ===
 
+            self.respond_sign_contract(request_id, &_request, response)
+        }
+
+        /// Store, log and announce a sign_contract response, then apply the failure mode to it
+        fn respond_sign_contract(
+            &mut self,
+            request_id: u64,
+            request: &SignContractRequest,
+            response: SignContractResponse,
+        ) -> Result<SignContractResponse> {
             // Log function call for audit trail
+            self.store_sign_contract(request_id, request, &response);
             self.log_function_call("sign_contract", request_id);
 
             self.env().emit_event(SignContractResponseGenerated {
@@ -871,6 +1355,7 @@ mod propertysale {
                 success: response.success,
             });
 
+            self.settle_rejection(&response.error)?;
             Ok(response)
         }
 
@@ -1163,6 +1648,10 @@ mod propertysale {
                 return Err(ContractError::Unauthorized);
             }
 
+            if self.disputes.open_about(&DisputeSubject::Offer).is_some() {
+                return Err(ContractError::DisputeOpen);
+            }
+
             let old_value = format!("{:?}", self.offer);
             let new_value_str = format!("{:?}", new_value);
             self.log_direct_field_change("offer", &old_value, &new_value_str);
@@ -1233,60 +1722,431 @@ mod propertysale {
             }
         }
 
-        // === AUDIT LOG FUNCTIONALITY ===
-
-        /// Record a function call in the audit log, including any pending field changes
-        fn log_function_call(&mut self, function_name: &str, request_id: u64) {
-            let caller = self.env().caller();
-            let timestamp = self.env().block_timestamp();
+        // === DRAFTING ===
 
-            // Take all pending field changes and include them in this function call entry
-            let field_changes = core::mem::take(&mut self.pending_field_changes);
+        /// Ask the draft service to render the agreement from the current contract data as
+        /// `output_format`. Returns the draft request id.
+        #[ink(message)]
+        pub fn request_draft(&mut self, output_format: DraftFormat) -> Result<u64> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
 
-            let log_entry = AuditLogEntry::FunctionCall {
-                caller,
-                timestamp,
-                function_name: function_name.to_string(),
-                request_id,
-                field_changes: field_changes.clone(),
-            };
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
 
-            // Store with current count as index, then increment
-            self.audit_log.insert(self.audit_log_count, &log_entry);
-            self.audit_log_count = self.audit_log_count.saturating_add(1);
+            let request_id = self.next_request_id();
 
-            self.env().emit_event(FunctionCalled {
-                caller,
-                function_name: function_name.to_string(),
+            self.env().emit_event(DraftRequested {
+                requester: caller,
                 request_id,
-                timestamp,
+                template_data: self.get_template_data(),
+                timestamp: self.env().block_timestamp(),
+                output_format,
+                template_class: TEMPLATE_CLASS.to_string(),
+                namespace: TEMPLATE_NAMESPACE.to_string(),
             });
 
-            // Emit individual field change events for each change
-            for field_change in field_changes {
-                self.env().emit_event(ContractDataChanged {
-                    field_name: field_change.field_name,
-                    changed_by: caller,
-                    old_value: field_change.old_value,
-                    new_value: field_change.new_value,
-                    block_number: self.env().block_number() as u64,
-                    timestamp,
-                });
-            }
+            self.log_function_call("request_draft", request_id);
+            Ok(request_id)
         }
 
-        /// Record a field change - adds to pending changes for inclusion in next function call log
-        fn log_field_change(&mut self, field_name: &str, old_value: &str, new_value: &str) {
-            let field_change = FieldChange {
-                field_name: field_name.to_string(),
-                old_value: old_value.to_string(),
-                new_value: new_value.to_string(),
-            };
-
-            self.pending_field_changes.push(field_change);
+        /// The contract data as Concerto JSON template data. The contract's own account is
+        /// used as the `contractId`.
+        #[ink(message)]
+        pub fn get_template_data(&self) -> String {
+            self.template_data(true)
         }
 
-        /// Record a direct field change immediately (for setter functions called directly)
+        /// Template data, optionally leaving out the signatures and status that change while
+        /// the agreed document is being signed
+        fn template_data(&self, signing_state: bool) -> String {
+            let parties = |parties: &[Party]| {
+                let items: Vec<String> = parties
+                    .iter()
+                    .map(|party| party.to_template_json(signing_state))
+                    .collect();
+                format!("[{}]", items.join(","))
+            };
+
+            let mut json = format!(
+                "{{\"$class\":\"{}\",\"contractId\":\"{}\",\"sellers\":{},\"buyers\":{},\"propertyAddress\":{}",
+                TEMPLATE_CLASS,
+                hex_string(self.env().account_id().as_ref()),
+                parties(&self.sellers),
+                parties(&self.buyers),
+                self.property_address.to_template_json()
+            );
+            for (name, money) in [
+                ("purchasePrice", &self.purchase_price),
+                ("deposit", &self.deposit),
+                ("balance", &self.balance),
+            ] {
+                if let Some(money) = money {
+                    json.push_str(&format!(",\"{}\":{}", name, money.to_template_json()));
+                }
+            }
+            if let Some(offer) = &self.offer {
+                json.push_str(&format!(",\"offer\":{}", offer.to_template_json()));
+            }
+            if let Some(agreement_date) = self.agreement_date {
+                json.push_str(&format!(",\"agreementDate\":{}", json_date_time(agreement_date)));
+            }
+            if signing_state {
+                json.push_str(&format!(",\"status\":\"{:?}\"", self.status));
+            }
+            json.push('}');
+            json
+        }
+
+        // === DOCUMENTS ===
+
+        /// Register the hash of a document rendered from the current contract data. Returns
+        /// the document's version.
+        #[ink(message)]
+        pub fn register_document(
+            &mut self,
+            document_hash: Hash,
+            algorithm: DocumentHashAlgorithm,
+        ) -> Result<u32> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.documents.contains(document_hash) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let version = self.document_count.saturating_add(1);
+            let template_data_hash = self.template_data_hash();
+            let record = DocumentRecord {
+                version,
+                document_hash,
+                algorithm,
+                template_data_hash,
+                submitter: caller,
+                timestamp: self.env().block_timestamp(),
+            };
+            self.documents.insert(document_hash, &record);
+            self.document_versions.insert(version, &document_hash);
+            self.document_count = version;
+
+            self.env().emit_event(DocumentRegistered {
+                document_hash,
+                version,
+                template_data_hash,
+                submitter: caller,
+            });
+
+            let request_id = self.next_request_id();
+            self.log_function_call("register_document", request_id);
+            Ok(version)
+        }
+
+        /// Whether a registered document was rendered from the current contract data
+        #[ink(message)]
+        pub fn verify_document(&self, document_hash: Hash) -> bool {
+            match self.documents.get(document_hash) {
+                Some(record) => record.template_data_hash == self.template_data_hash(),
+                None => false,
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_document(&self, document_hash: Hash) -> Option<DocumentRecord> {
+            self.documents.get(document_hash)
+        }
+
+        #[ink(message)]
+        pub fn get_latest_document(&self) -> Option<DocumentRecord> {
+            let document_hash = self.document_versions.get(self.document_count)?;
+            self.documents.get(document_hash)
+        }
+
+        /// Blake2x256 of the template data without signing state, so signing does not change it
+        fn template_data_hash(&self) -> Hash {
+            Self::blake2x256(self.template_data(false).as_bytes())
+        }
+
+        // === DISPUTES ===
+
+        /// A buyer or seller asks the arbitrator to decide a disagreement, freezing the
+        /// operations it affects until it is resolved. A resolved subject can be disputed again
+        /// only after `DISPUTE_COOLDOWN`. Returns the dispute id.
+        #[ink(message)]
+        pub fn open_dispute(
+            &mut self,
+            subject: DisputeSubject,
+            evidence_hash: Hash,
+        ) -> Result<u64> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if !self.is_caller_buyer(caller) && !self.is_caller_seller(caller) {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let valid_subject = match &subject {
+                DisputeSubject::Offer => self.offer.is_some(),
+                DisputeSubject::Signature { party_id } => self
+                    .sellers
+                    .iter()
+                    .chain(self.buyers.iter())
+                    .any(|party| party.party_id == *party_id && party.signed_at.is_some()),
+            };
+            if !valid_subject || evidence_hash == Hash::default() {
+                return Err(ContractError::InvalidInput);
+            }
+            let now = self.env().block_timestamp();
+            let dispute_id = self
+                .disputes
+                .open(subject.clone(), caller, evidence_hash, now)
+                .ok_or(ContractError::InvalidInput)?
+                .dispute_id;
+
+            self.env().emit_event(DisputeOpened {
+                dispute_id,
+                opened_by: caller,
+                subject,
+                evidence_hash,
+            });
+            let request_id = self.next_request_id();
+            self.log_function_call("open_dispute", request_id);
+            Ok(dispute_id)
+        }
+
+        /// Arbitrator settles an open dispute with an outcome that fits its subject
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, dispute_id: u64, outcome: DisputeOutcome) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let arbitrator = self.env().caller();
+            if arbitrator != self.arbitrator {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let dispute = self.disputes.get(dispute_id).ok_or(ContractError::InvalidInput)?;
+            let fits_subject = matches!(
+                (&dispute.subject, &outcome),
+                (_, DisputeOutcome::Dismissed)
+                    | (DisputeSubject::Offer, DisputeOutcome::RevertOffer)
+                    | (DisputeSubject::Signature { .. }, DisputeOutcome::VoidSignature)
+            );
+            if dispute.outcome.is_some() || !fits_subject {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let old_sellers = format!("{:?}", self.sellers);
+            let old_buyers = format!("{:?}", self.buyers);
+            match (&dispute.subject, &outcome) {
+                (DisputeSubject::Offer, DisputeOutcome::RevertOffer) => {
+                    let old_offer = format!("{:?}", self.offer);
+                    if let Some(ref mut offer) = self.offer {
+                        offer.offer_status = OfferStatus::Pending;
+                    }
+                    let new_offer = format!("{:?}", self.offer);
+                    self.log_field_change("offer", &old_offer, &new_offer);
+                    for party in self.sellers.iter_mut().chain(self.buyers.iter_mut()) {
+                        party.signed_at = None;
+                    }
+                }
+                (DisputeSubject::Signature { party_id }, DisputeOutcome::VoidSignature) => {
+                    for party in self.sellers.iter_mut().chain(self.buyers.iter_mut()) {
+                        if party.party_id == *party_id {
+                            party.signed_at = None;
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            let new_sellers = format!("{:?}", self.sellers);
+            if new_sellers != old_sellers {
+                self.log_field_change("sellers", &old_sellers, &new_sellers);
+            }
+            let new_buyers = format!("{:?}", self.buyers);
+            if new_buyers != old_buyers {
+                self.log_field_change("buyers", &old_buyers, &new_buyers);
+            }
+
+            // Voided signatures take the contract back to signing, or to the offer stage
+            if outcome != DisputeOutcome::Dismissed {
+                let new_status = if self.is_first_signature() {
+                    ContractStatus::UnderOffer
+                } else {
+                    ContractStatus::Signing
+                };
+                if self.status != new_status {
+                    let old_status = format!("{:?}", self.status);
+                    self.status = new_status;
+                    let new_status = format!("{:?}", self.status);
+                    self.log_field_change("status", &old_status, &new_status);
+                }
+            }
+
+            let now = self.env().block_timestamp();
+            self.disputes
+                .resolve(dispute_id, outcome.clone(), now)
+                .ok_or(ContractError::InvalidInput)?;
+            self.env().emit_event(DisputeResolved {
+                dispute_id,
+                arbitrator,
+                outcome,
+            });
+            let request_id = self.next_request_id();
+            self.log_function_call("resolve_dispute", request_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_dispute(&self, dispute_id: u64) -> Option<Dispute> {
+            self.disputes.get(dispute_id)
+        }
+
+        /// Dispute history, oldest first
+        #[ink(message)]
+        pub fn get_disputes(&self, start: u64, limit: u64) -> Vec<Dispute> {
+            self.disputes.range(start, limit)
+        }
+
+        #[ink(message)]
+        pub fn get_dispute_count(&self) -> u64 {
+            self.disputes.count()
+        }
+
+        #[ink(message)]
+        pub fn get_open_disputes(&self) -> Vec<Dispute> {
+            self.disputes.open_disputes()
+        }
+
+        // === REQUEST HISTORY ===
+
+        /// Allocate the next request id; ids are never reused, even within a block
+        fn next_request_id(&mut self) -> u64 {
+            let request_id = self.request_count;
+            self.request_count = self.request_count.saturating_add(1);
+            request_id
+        }
+
+        fn store_manage_offer(
+            &mut self,
+            request_id: u64,
+            request: &ManageOfferRequest,
+            response: &ManageOfferResponse,
+        ) {
+            let record = RequestRecord::ManageOffer {
+                request_id,
+                submitter: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+                request: request.clone(),
+                response: response.clone(),
+            };
+            self.requests.insert(request_id, &record);
+        }
+
+        fn store_sign_contract(
+            &mut self,
+            request_id: u64,
+            request: &SignContractRequest,
+            response: &SignContractResponse,
+        ) {
+            let record = RequestRecord::SignContract {
+                request_id,
+                submitter: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+                request: request.clone(),
+                response: response.clone(),
+            };
+            self.requests.insert(request_id, &record);
+        }
+
+        #[ink(message)]
+        pub fn get_request_count(&self) -> u64 {
+            self.request_count
+        }
+
+        #[ink(message)]
+        pub fn get_request(&self, request_id: u64) -> Option<RequestRecord> {
+            self.requests.get(request_id)
+        }
+
+        #[ink(message)]
+        pub fn get_requests(&self, start: u64, limit: u64) -> Vec<RequestRecord> {
+            let mut records = Vec::new();
+            let end = start.saturating_add(limit).min(self.request_count);
+
+            for i in start..end {
+                if let Some(record) = self.requests.get(i) {
+                    records.push(record);
+                }
+            }
+
+            records
+        }
+
+        // === AUDIT LOG FUNCTIONALITY ===
+
+        /// Record a function call in the audit log, including any pending field changes
+        fn log_function_call(&mut self, function_name: &str, request_id: u64) {
+            let caller = self.env().caller();
+            let timestamp = self.env().block_timestamp();
+
+            // Take all pending field changes and include them in this function call entry
+            let field_changes = core::mem::take(&mut self.pending_field_changes);
+
+            let log_entry = AuditLogEntry::FunctionCall {
+                caller,
+                timestamp,
+                function_name: function_name.to_string(),
+                request_id,
+                field_changes: field_changes.clone(),
+            };
+
+            self.append_audit_entry(&log_entry);
+
+            self.env().emit_event(FunctionCalled {
+                caller,
+                function_name: function_name.to_string(),
+                request_id,
+                timestamp,
+            });
+
+            // Emit individual field change events for each change
+            for field_change in field_changes {
+                self.env().emit_event(ContractDataChanged {
+                    field_name: field_change.field_name,
+                    changed_by: caller,
+                    old_value: field_change.old_value,
+                    new_value: field_change.new_value,
+                    block_number: self.env().block_number() as u64,
+                    timestamp,
+                });
+            }
+        }
+
+        /// Record a field change - adds to pending changes for inclusion in next function call log
+        fn log_field_change(&mut self, field_name: &str, old_value: &str, new_value: &str) {
+            let field_change = FieldChange {
+                field_name: field_name.to_string(),
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+            };
+
+            self.pending_field_changes.push(field_change);
+        }
+
+        /// Record a direct field change immediately (for setter functions called directly)
         fn log_direct_field_change(&mut self, field_name: &str, old_value: &str, new_value: &str) {
             let caller = self.env().caller();
             let timestamp = self.env().block_timestamp();
@@ -1301,8 +2161,7 @@ mod propertysale {
                 timestamp,
             };
 
-            self.audit_log.insert(self.audit_log_count, &log_entry);
-            self.audit_log_count = self.audit_log_count.saturating_add(1);
+            self.append_audit_entry(&log_entry);
 
             // Emit event
             self.env().emit_event(ContractDataChanged {
@@ -1315,6 +2174,85 @@ mod propertysale {
             });
         }
 
+        /// Store an entry at the next index, extend the hash chain over it and index it
+        fn append_audit_entry(&mut self, log_entry: &AuditLogEntry) {
+            let index = self.audit_log_count;
+            let hash = Self::chain_audit_hash(&self.audit_log_head, log_entry);
+
+            // Store with current count as index, then increment
+            self.audit_log.insert(index, log_entry);
+            self.audit_log_hashes.insert(index, &hash);
+            self.audit_log_head = hash;
+            self.audit_log_count = self.audit_log_count.saturating_add(1);
+
+            let caller = log_entry.caller();
+            let position = self.audit_log_caller_count.get(caller).unwrap_or(0);
+            self.audit_log_by_caller.insert((caller, position), &index);
+            self.audit_log_caller_count.insert(caller, &position.saturating_add(1));
+
+            if let AuditLogEntry::FunctionCall { function_name, .. } = log_entry {
+                let position = self.audit_log_function_count.get(function_name).unwrap_or(0);
+                self.audit_log_by_function.insert((function_name.clone(), position), &index);
+                self.audit_log_function_count.insert(function_name, &position.saturating_add(1));
+            }
+        }
+
+        /// Blake2x256 over the previous hash followed by the SCALE-encoded entry
+        fn chain_audit_hash(previous: &Hash, log_entry: &AuditLogEntry) -> Hash {
+            let mut input = Vec::from(previous.as_ref());
+            scale::Encode::encode_to(log_entry, &mut input);
+            Self::blake2x256(&input)
+        }
+
+        fn blake2x256(input: &[u8]) -> Hash {
+            let mut output = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(input, &mut output);
+            Hash::from(output)
+        }
+
+        /// Hash of the latest audit log entry; all zeroes while the log is empty
+        #[ink(message)]
+        pub fn get_audit_log_head(&self) -> Hash {
+            self.audit_log_head
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_hash(&self, index: u64) -> Option<Hash> {
+            self.audit_log_hashes.get(index)
+        }
+
+        /// Recompute the hash chain over entries `start..end` and check it against the stored
+        /// hashes. Returns false if the range is out of bounds or any entry is missing or altered.
+        #[ink(message)]
+        pub fn verify_audit_range(&self, start: u64, end: u64) -> bool {
+            if start < self.audit_log_start || start > end || end > self.audit_log_count {
+                return false;
+            }
+
+            let mut hash = if start == 0 {
+                Hash::default()
+            } else if start == self.audit_log_start {
+                self.audit_archived_head
+            } else {
+                match self.audit_log_hashes.get(start - 1) {
+                    Some(hash) => hash,
+                    None => return false,
+                }
+            };
+
+            for i in start..end {
+                let Some(entry) = self.audit_log.get(i) else {
+                    return false;
+                };
+                hash = Self::chain_audit_hash(&hash, &entry);
+                if self.audit_log_hashes.get(i) != Some(hash) {
+                    return false;
+                }
+            }
+
+            true
+        }
+
         #[ink(message)]
         pub fn get_audit_log_count(&self) -> u64 {
             self.audit_log_count
@@ -1334,6 +2272,129 @@ mod propertysale {
             entries
         }
 
+        /// Entries made by `caller` with timestamps in `from..to`.
+        ///
+        /// Pass `None` as the cursor for the first page, then the returned `next_cursor`.
+        #[ink(message)]
+        pub fn get_audit_log_by_caller(
+            &self,
+            caller: AccountId,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let first = self.audit_log_caller_start.get(caller).unwrap_or(0);
+            let len = self.audit_log_caller_count.get(caller).unwrap_or(0);
+            self.audit_log_page(
+                first..len,
+                |position| self.audit_log_by_caller.get((caller, position)),
+                from,
+                to,
+                cursor,
+                limit,
+            )
+        }
+
+        /// Calls of `function_name` with timestamps in `from..to`, paginated as
+        /// `get_audit_log_by_caller`
+        #[ink(message)]
+        pub fn get_audit_log_by_function(
+            &self,
+            function_name: String,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let first = self.audit_log_function_start.get(&function_name).unwrap_or(0);
+            let len = self.audit_log_function_count.get(&function_name).unwrap_or(0);
+            self.audit_log_page(
+                first..len,
+                |position| self.audit_log_by_function.get((function_name.clone(), position)),
+                from,
+                to,
+                cursor,
+                limit,
+            )
+        }
+
+        /// All entries with timestamps in `from..to`, paginated as `get_audit_log_by_caller`
+        #[ink(message)]
+        pub fn get_audit_log_by_time(
+            &self,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let positions = self.audit_log_start..self.audit_log_count;
+            self.audit_log_page(positions, Some, from, to, cursor, limit)
+        }
+
+        /// Page through the retained `positions` of an index, where `log_index` maps a position
+        /// to its audit log index.
+        ///
+        /// Entries are appended in block order, so timestamps never decrease along the log or
+        /// any index into it. The first page binary searches for `from` and every page stops
+        /// at `to`, so only the returned entries and a logarithmic number of others are read.
+        fn audit_log_page(
+            &self,
+            positions: core::ops::Range<u64>,
+            log_index: impl Fn(u64) -> Option<u64>,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let timestamp_at = |position: u64| {
+                log_index(position)
+                    .and_then(|index| self.audit_log.get(index))
+                    .map(|entry| entry.timestamp())
+            };
+
+            let len = positions.end;
+            let mut position = match cursor {
+                Some(cursor) => cursor.max(positions.start),
+                None => {
+                    let (mut low, mut high) = (positions.start, len);
+                    while low < high {
+                        let mid = low + (high - low) / 2;
+                        if timestamp_at(mid).unwrap_or(0) < from {
+                            low = mid + 1;
+                        } else {
+                            high = mid;
+                        }
+                    }
+                    low
+                }
+            };
+
+            let mut entries = Vec::new();
+            while position < len {
+                if entries.len() as u64 >= limit {
+                    return AuditLogPage {
+                        entries,
+                        next_cursor: Some(position),
+                    };
+                }
+                if let Some(index) = log_index(position) {
+                    if let Some(entry) = self.audit_log.get(index) {
+                        if entry.timestamp() >= to {
+                            break;
+                        }
+                        entries.push((index, entry));
+                    }
+                }
+                position = position.saturating_add(1);
+            }
+
+            AuditLogPage {
+                entries,
+                next_cursor: None,
+            }
+        }
+
         #[ink(message)]
         pub fn get_audit_log_function_calls(&self, start: u64, limit: u64) -> Vec<AuditLogEntry> {
             let mut entries = Vec::new();
@@ -1418,12 +2479,274 @@ mod propertysale {
 
             matching_entries
         }
+
+        // === AUDIT LOG RETENTION ===
+
+        #[ink(message)]
+        pub fn set_audit_retention(&mut self, new_value: AuditRetention) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if new_value.max_entries == Some(0) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let old_value = format!("{:?}", self.audit_retention);
+            let new_value_str = format!("{:?}", new_value);
+            self.log_direct_field_change("audit_retention", &old_value, &new_value_str);
+            self.audit_retention = new_value;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_audit_retention(&self) -> AuditRetention {
+            self.audit_retention.clone()
+        }
+
+        /// Archive up to `limit` of the oldest entries the retention policy allows to be
+        /// pruned: commit them to a Merkle root checkpoint, then remove them from storage.
+        ///
+        /// Returns the number of entries archived. Nothing is archived when the policy
+        /// retains every entry.
+        #[ink(message)]
+        pub fn archive_audit_log(&mut self, limit: u64) -> Result<u64> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let start = self.audit_log_start;
+            let now = self.env().block_timestamp();
+            let max_end = start
+                .saturating_add(limit.min(MAX_AUDIT_ARCHIVE_BATCH))
+                .min(self.audit_log_count);
+
+            // Entries beyond the retained count, and then entries past the maximum age; both
+            // form a prefix of the log because timestamps never decrease
+            let mut end = match self.audit_retention.max_entries {
+                Some(max_entries) => self.audit_log_count.saturating_sub(max_entries),
+                None => 0,
+            }
+            .clamp(start, max_end);
+            if let Some(max_age) = self.audit_retention.max_age {
+                while end < max_end {
+                    match self.audit_log.get(end) {
+                        Some(entry) if now.saturating_sub(entry.timestamp()) > max_age => {
+                            end += 1
+                        }
+                        _ => break,
+                    }
+                }
+            }
+            if end == start {
+                return Ok(0);
+            }
+
+            let mut leaves = Vec::new();
+            for index in start..end {
+                let entry = self.audit_log.take(index).ok_or(ContractError::ProcessingFailed)?;
+                leaves.push(Self::audit_leaf_hash(index, &entry));
+                self.prune_audit_indexes(&entry);
+            }
+            let head = self
+                .audit_log_hashes
+                .get(end - 1)
+                .ok_or(ContractError::ProcessingFailed)?;
+            for index in start..end {
+                self.audit_log_hashes.remove(index);
+            }
+
+            let checkpoint_id = self.audit_checkpoint_count;
+            let root = Self::audit_merkle_root(leaves);
+            let checkpoint = AuditCheckpoint {
+                start,
+                end,
+                root,
+                head,
+                archived_by: caller,
+                archived_at: now,
+            };
+            self.audit_checkpoints.insert(checkpoint_id, &checkpoint);
+            self.audit_checkpoint_count = self.audit_checkpoint_count.saturating_add(1);
+            self.audit_log_start = end;
+            self.audit_archived_head = head;
+
+            self.env().emit_event(AuditLogArchived {
+                checkpoint_id,
+                start,
+                end,
+                root,
+            });
+            Ok(end - start)
+        }
+
+        /// Drop a pruned entry's positions from the caller and function indexes. Entries are
+        /// pruned in log order, so each is the oldest retained position of its index.
+        fn prune_audit_indexes(&mut self, log_entry: &AuditLogEntry) {
+            let caller = log_entry.caller();
+            let position = self.audit_log_caller_start.get(caller).unwrap_or(0);
+            self.audit_log_by_caller.remove((caller, position));
+            self.audit_log_caller_start.insert(caller, &position.saturating_add(1));
+
+            if let AuditLogEntry::FunctionCall { function_name, .. } = log_entry {
+                let position = self.audit_log_function_start.get(function_name).unwrap_or(0);
+                self.audit_log_by_function.remove((function_name.clone(), position));
+                self.audit_log_function_start.insert(function_name, &position.saturating_add(1));
+            }
+        }
+
+        /// Merkle leaf for an archived entry: Blake2x256 over `0x00` and the SCALE-encoded
+        /// `(index, entry)`
+        fn audit_leaf_hash(index: u64, log_entry: &AuditLogEntry) -> Hash {
+            let mut input = Vec::from([0u8]);
+            scale::Encode::encode_to(&(index, log_entry), &mut input);
+            Self::blake2x256(&input)
+        }
+
+        /// Merkle node: Blake2x256 over `0x01` and both children
+        fn audit_node_hash(left: &Hash, right: &Hash) -> Hash {
+            let mut input = Vec::from([1u8]);
+            input.extend_from_slice(left.as_ref());
+            input.extend_from_slice(right.as_ref());
+            Self::blake2x256(&input)
+        }
+
+        /// Root over `leaves`, pairing neighbours level by level and carrying an unpaired
+        /// last node up unchanged
+        fn audit_merkle_root(mut level: Vec<Hash>) -> Hash {
+            while level.len() > 1 {
+                level = level
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [left, right] => Self::audit_node_hash(left, right),
+                        _ => pair[0],
+                    })
+                    .collect();
+            }
+            level.first().copied().unwrap_or_default()
+        }
+
+        /// Check that `entry` was archived at `index` under a checkpoint. `proof` lists the
+        /// sibling hashes from the leaf up to the root, skipping levels where the node is
+        /// carried up unpaired.
+        #[ink(message)]
+        pub fn verify_audit_proof(
+            &self,
+            checkpoint_id: u64,
+            index: u64,
+            entry: AuditLogEntry,
+            proof: Vec<Hash>,
+        ) -> bool {
+            let Some(checkpoint) = self.audit_checkpoints.get(checkpoint_id) else {
+                return false;
+            };
+            if index < checkpoint.start || index >= checkpoint.end {
+                return false;
+            }
+
+            let mut position = index - checkpoint.start;
+            let mut width = checkpoint.end - checkpoint.start;
+            let mut hash = Self::audit_leaf_hash(index, &entry);
+            let mut siblings = proof.iter();
+            while width > 1 {
+                if position % 2 == 1 {
+                    let Some(sibling) = siblings.next() else {
+                        return false;
+                    };
+                    hash = Self::audit_node_hash(sibling, &hash);
+                } else if position + 1 < width {
+                    let Some(sibling) = siblings.next() else {
+                        return false;
+                    };
+                    hash = Self::audit_node_hash(&hash, sibling);
+                }
+                position /= 2;
+                width = width.div_ceil(2);
+            }
+
+            siblings.next().is_none() && hash == checkpoint.root
+        }
+
+        #[ink(message)]
+        pub fn get_audit_checkpoint(&self, checkpoint_id: u64) -> Option<AuditCheckpoint> {
+            self.audit_checkpoints.get(checkpoint_id)
+        }
+
+        #[ink(message)]
+        pub fn get_audit_checkpoint_count(&self) -> u64 {
+            self.audit_checkpoint_count
+        }
+
+        /// Index of the oldest entry still in storage
+        #[ink(message)]
+        pub fn get_audit_log_start(&self) -> u64 {
+            self.audit_log_start
+        }
     }
 
     #[cfg(test)]
+    #[allow(clippy::bool_assert_comparison)]
     mod tests {
         use super::*;
 
+        /// Party with placeholder contact details
+        fn party(party_id: &str, wallet_address: AccountId) -> Party {
+            Party {
+                party_id: party_id.to_string(),
+                full_name: party_id.to_string(),
+                email: "party@example.com".to_string(),
+                mobile: String::new(),
+                address: PropertyAddress::default(),
+                wallet_address,
+                signed_at: None,
+            }
+        }
+
+        fn gbp(amount: u128) -> Money {
+            Money {
+                amount: Decimal::from_integer(amount).unwrap(),
+                currency_code: CurrencyCode::GBP,
+            }
+        }
+
+        /// Sale for £650,000 owned by alice, from bob (SELLER_001) to charlie (BUYER_001) with
+        /// django as arbitrator, reporting rejections in responses
+        fn sale(offer: Option<Offer>, status: ContractStatus) -> PropertySale {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            PropertySale::new(
+                vec![party("SELLER_001", accounts.bob)],
+                vec![party("BUYER_001", accounts.charlie)],
+                PropertyAddress::default(),
+                Some(gbp(650000)),
+                None,
+                None,
+                offer,
+                None,
+                status,
+                FailureMode::Respond,
+                accounts.django,
+            )
+        }
+
+        /// Offer at the sale's purchase price that the seller has accepted
+        fn accepted_offer() -> Offer {
+            Offer {
+                offer: gbp(650000),
+                offer_status: OfferStatus::Accepted,
+                offer_date: 0,
+            }
+        }
+
         #[ink::test]
         fn default_works() {
             let contract = PropertySale::default();
@@ -1444,5 +2767,477 @@ mod propertysale {
             assert_eq!(contract.unpause(), Ok(()));
             assert_eq!(contract.is_paused(), false);
         }
+
+        #[ink::test]
+        fn money_amounts_keep_pence() {
+            let mut contract = PropertySale::default();
+            let price = Money {
+                amount: Decimal::from_parts(65000099, 2).unwrap(),
+                currency_code: CurrencyCode::GBP,
+            };
+            assert_eq!(contract.set_purchase_price(Some(price.clone())), Ok(()));
+            assert_eq!(contract.get_purchase_price(), Some(price));
+
+            let deposit = Decimal::from_integer(650000)
+                .unwrap()
+                .percentage_of(Decimal::from_parts(125, 1).unwrap(), Rounding::HalfUp)
+                .unwrap();
+            assert_eq!(deposit.to_string(), "81250");
+        }
+
+        #[ink::test]
+        fn requests_in_the_same_block_get_distinct_ids() {
+            let mut contract = PropertySale::default();
+            let offer = ManageOfferRequest {
+                action: OfferAction::Submit,
+                offer: None,
+            };
+            let rejected = contract.manage_offer(offer.clone()).unwrap();
+            assert!(!rejected.success);
+            let unsigned = contract.sign_contract(SignContractRequest {}).unwrap();
+            assert_eq!(unsigned.error, Some(SaleError::NoSellers));
+
+            assert_eq!(contract.get_request_count(), 2);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(
+                contract.get_request(0),
+                Some(RequestRecord::ManageOffer {
+                    request_id: 0,
+                    submitter: accounts.alice,
+                    timestamp: 0,
+                    request: offer,
+                    response: rejected,
+                })
+            );
+            assert!(matches!(
+                contract.get_request(1),
+                Some(RequestRecord::SignContract { request_id: 1, .. })
+            ));
+            assert_eq!(contract.get_requests(1, 10).len(), 1);
+            assert_eq!(contract.get_request(2), None);
+        }
+
+        #[ink::test]
+        fn audit_log_is_hash_chained() {
+            let mut contract = PropertySale::default();
+            assert_eq!(contract.get_audit_log_head(), Hash::default());
+            assert!(contract.verify_audit_range(0, 0));
+
+            assert_eq!(contract.set_agreement_date(Some(1703980800000)), Ok(()));
+            assert_eq!(contract.set_status(ContractStatus::UnderOffer), Ok(()));
+            assert_eq!(contract.set_agreement_date(None), Ok(()));
+            let count = contract.get_audit_log_count();
+            assert_eq!(count, 3);
+
+            let mut hash = Hash::default();
+            for (i, entry) in contract.get_audit_log(0, count).iter().enumerate() {
+                hash = PropertySale::chain_audit_hash(&hash, entry);
+                assert_eq!(contract.get_audit_log_hash(i as u64), Some(hash));
+            }
+            assert_eq!(contract.get_audit_log_head(), hash);
+            assert!(contract.verify_audit_range(0, count));
+            assert!(contract.verify_audit_range(1, count));
+            assert!(!contract.verify_audit_range(0, count + 1));
+
+            // Rewriting a stored entry breaks the chain from that entry onwards
+            let tampered = AuditLogEntry::DirectFieldChange {
+                field_name: "status".to_string(),
+                changed_by: contract.get_owner(),
+                old_value: String::new(),
+                new_value: String::new(),
+                block_number: 0,
+                timestamp: 0,
+            };
+            contract.audit_log.insert(0, &tampered);
+            assert!(!contract.verify_audit_range(0, count));
+            assert!(contract.verify_audit_range(1, count));
+        }
+
+        #[ink::test]
+        fn audit_log_is_queried_by_caller_and_time() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = PropertySale::default();
+            for (timestamp, status) in [
+                (1000, ContractStatus::UnderOffer),
+                (2000, ContractStatus::Signing),
+                (3000, ContractStatus::Signed),
+            ] {
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(timestamp);
+                assert_eq!(contract.set_status(status), Ok(()));
+            }
+
+            let page = contract.get_audit_log_by_time(1500, 3500, None, 1);
+            assert_eq!(page.entries.len(), 1);
+            assert_eq!(page.entries[0].0, 1);
+            let page = contract.get_audit_log_by_time(1500, 3500, page.next_cursor, 1);
+            assert_eq!(page.entries[0].0, 2);
+
+            let page = contract.get_audit_log_by_caller(accounts.alice, 0, 2000, None, 10);
+            assert_eq!(page.entries.len(), 1);
+            assert_eq!(page.next_cursor, None);
+            assert!(contract
+                .get_audit_log_by_caller(accounts.bob, 0, u64::MAX, None, 10)
+                .entries
+                .is_empty());
+        }
+
+        #[ink::test]
+        fn audit_log_is_archived_under_retention_policy() {
+            let mut contract = PropertySale::default();
+            assert_eq!(contract.set_status(ContractStatus::UnderOffer), Ok(()));
+            assert_eq!(contract.set_status(ContractStatus::Signing), Ok(()));
+            let entries = contract.get_audit_log(0, 10);
+
+            let keep_one = AuditRetention {
+                max_age: None,
+                max_entries: Some(1),
+            };
+            assert_eq!(contract.set_audit_retention(keep_one), Ok(()));
+            assert_eq!(contract.archive_audit_log(10), Ok(2));
+            assert_eq!(contract.get_audit_log_start(), 2);
+            assert!(contract.verify_audit_range(2, 3));
+
+            // With two leaves, each proof is the other leaf
+            let leaves: Vec<Hash> = entries
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| PropertySale::audit_leaf_hash(i as u64, entry))
+                .collect();
+            assert_eq!(
+                contract.get_audit_checkpoint(0).unwrap().root,
+                PropertySale::audit_node_hash(&leaves[0], &leaves[1])
+            );
+            assert!(contract.verify_audit_proof(0, 1, entries[1].clone(), vec![leaves[0]]));
+            assert!(!contract.verify_audit_proof(0, 1, entries[0].clone(), vec![leaves[0]]));
+            assert!(!contract.verify_audit_proof(0, 2, entries[1].clone(), vec![leaves[0]]));
+        }
+
+        #[ink::test]
+        fn draft_request_carries_concerto_template_data() {
+            type Env = ink::env::DefaultEnvironment;
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let address = PropertyAddress {
+                address_line1: "55 Broadway".to_string(),
+                address_line2: "Westminster".to_string(),
+                city: "London".to_string(),
+                post_code: "SW1H 0BD".to_string(),
+                county: "Greater London".to_string(),
+                country: Country::UK,
+            };
+            let seller = Party {
+                party_id: "SELLER_001".to_string(),
+                full_name: "Transport for London".to_string(),
+                email: "property.sales@tfl.gov.uk".to_string(),
+                mobile: "+44 20 7222 1234".to_string(),
+                address: address.clone(),
+                wallet_address: AccountId::from([0x12; 32]),
+                signed_at: Some(1705329000000),
+            };
+            let price = Money {
+                amount: Decimal::from_integer(650000).unwrap(),
+                currency_code: CurrencyCode::GBP,
+            };
+            let mut contract = PropertySale::new(
+                vec![seller],
+                Vec::new(),
+                address,
+                Some(price),
+                None,
+                None,
+                None,
+                Some(1705312800000),
+                ContractStatus::Signed,
+                FailureMode::Respond,
+                accounts.django,
+            );
+
+            let address_json = concat!(
+                r#"{"$class":"realestatesaleuk@1.0.0.Address","addressLine1":"55 Broadway","#,
+                r#""addressLine2":"Westminster","city":"London","postCode":"SW1H 0BD","#,
+                r#""county":"Greater London","country":"UK"}"#
+            );
+            let expected = format!(
+                concat!(
+                    r#"{{"$class":"realestatesaleuk@1.0.0.PropertySale","contractId":"{}","#,
+                    r#""sellers":[{{"$class":"realestatesaleuk@1.0.0.Party","partyId":"SELLER_001","#,
+                    r#""fullName":"Transport for London","email":"property.sales@tfl.gov.uk","#,
+                    r#""mobile":"+44 20 7222 1234","address":{},"walletAddress":"0x{}","#,
+                    r#""signedAt":"2024-01-15T14:30:00.000Z"}}],"buyers":[],"propertyAddress":{},"#,
+                    r#""purchasePrice":{{"$class":"realestatesaleuk@1.0.0.Money","amount":650000,"currencyCode":"GBP"}},"#,
+                    r#""agreementDate":"2024-01-15T10:00:00.000Z","status":"Signed"}}"#
+                ),
+                hex_string(ink::env::account_id::<Env>().as_ref()),
+                address_json,
+                "12".repeat(32),
+                address_json
+            );
+            assert_eq!(contract.get_template_data(), expected);
+
+            assert_eq!(contract.request_draft(DraftFormat::Pdf), Ok(0));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let unauthorized = contract.request_draft(DraftFormat::Markdown);
+            assert_eq!(unauthorized, Err(ContractError::Unauthorized));
+
+            let event = ink::env::test::recorded_events()
+                .filter_map(|event| <DraftRequested as scale::Decode>::decode(&mut &event.data[..]).ok())
+                .last()
+                .unwrap();
+            assert_eq!(event.requester, accounts.alice);
+            assert_eq!(event.template_data, expected);
+            assert_eq!(event.output_format, DraftFormat::Pdf);
+            assert_eq!(event.namespace, TEMPLATE_NAMESPACE);
+        }
+
+        #[ink::test]
+        fn signing_requires_the_latest_document_to_match() {
+            type Env = ink::env::DefaultEnvironment;
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = sale(Some(accepted_offer()), ContractStatus::UnderOffer);
+
+            let first = Hash::from([1; 32]);
+            assert_eq!(contract.register_document(first, DocumentHashAlgorithm::Sha2x256), Ok(1));
+            assert_eq!(
+                contract.register_document(first, DocumentHashAlgorithm::Sha2x256),
+                Err(ContractError::InvalidInput)
+            );
+
+            // Signing changes signatures and status but not the agreed document
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert!(contract.sign_contract(SignContractRequest {}).unwrap().success);
+            assert!(contract.verify_document(first));
+            assert_eq!(
+                contract.get_audit_log_field_changes_by_field("signed_document".to_string()).len(),
+                1
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.set_deposit(Some(gbp(65000))), Ok(()));
+            assert!(!contract.verify_document(first));
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let response = contract.sign_contract(SignContractRequest {}).unwrap();
+            assert!(!response.success);
+            assert_eq!(response.error, Some(SaleError::DocumentOutdated));
+
+            let second = Hash::from([2; 32]);
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.register_document(second, DocumentHashAlgorithm::Blake2x256), Ok(2));
+            assert_eq!(contract.get_latest_document().unwrap().document_hash, second);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert!(contract.sign_contract(SignContractRequest {}).unwrap().success);
+            assert_eq!(contract.get_status(), ContractStatus::Signed);
+        }
+
+        #[ink::test]
+        fn rejected_requests_report_typed_errors() {
+            type Env = ink::env::DefaultEnvironment;
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = sale(None, ContractStatus::Draft);
+            let request = |action, offer| ManageOfferRequest { action, offer };
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let response = contract.manage_offer(request(OfferAction::Submit, Some(gbp(640000))));
+            assert_eq!(response.unwrap().error, Some(SaleError::NotABuyer));
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let response = contract.manage_offer(request(OfferAction::Accept, None));
+            assert_eq!(response.unwrap().error, Some(SaleError::NotASeller));
+            let response = contract.manage_offer(request(OfferAction::Cancel, None));
+            assert_eq!(response.unwrap().error, Some(SaleError::NoOffer));
+            let signed = contract.sign_contract(SignContractRequest {}).unwrap();
+            assert_eq!(signed.error, Some(SaleError::NoOffer));
+
+            let response = contract.manage_offer(request(OfferAction::Submit, Some(gbp(640000))));
+            assert_eq!(response.unwrap().error, None);
+            let signed = contract.sign_contract(SignContractRequest {}).unwrap();
+            assert_eq!(
+                signed.error,
+                Some(SaleError::OfferNotAccepted {
+                    status: OfferStatus::Pending
+                })
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let response = contract.manage_offer(request(OfferAction::Accept, None));
+            assert!(response.unwrap().success);
+            let signed = contract.sign_contract(SignContractRequest {}).unwrap();
+            assert_eq!(
+                signed.error,
+                Some(SaleError::PurchasePriceMismatch {
+                    expected: gbp(640000),
+                    actual: gbp(650000),
+                })
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.set_purchase_price(Some(gbp(640000))), Ok(()));
+            let signed = contract.sign_contract(SignContractRequest {}).unwrap();
+            assert_eq!(signed.error, Some(SaleError::NotAParty));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert!(contract.sign_contract(SignContractRequest {}).unwrap().success);
+            let signed = contract.sign_contract(SignContractRequest {}).unwrap();
+            assert_eq!(signed.error, Some(SaleError::AlreadySigned));
+
+            // Unauthorized callers are logged and announced like every other rejection
+            let logged = |function_name: &str| {
+                let function_name = function_name.to_string();
+                let page = contract.get_audit_log_by_function(function_name, 0, u64::MAX, None, 10);
+                page.entries.len()
+            };
+            assert_eq!((logged("manage_offer"), logged("sign_contract")), (5, 6));
+            let announced = |topic: Option<[u8; 32]>| {
+                let topic = topic.unwrap();
+                ink::env::test::recorded_events()
+                    .filter(|event| event.topics.first().is_some_and(|first| first[..] == topic))
+                    .count()
+            };
+            let offers = <ManageOfferResponseGenerated as ink::env::Event>::SIGNATURE_TOPIC;
+            let signatures = <SignContractResponseGenerated as ink::env::Event>::SIGNATURE_TOPIC;
+            assert_eq!((announced(offers), announced(signatures)), (5, 6));
+        }
+
+        #[ink::test]
+        fn revert_mode_returns_rejections_as_errors() {
+            type Env = ink::env::DefaultEnvironment;
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = PropertySale::default();
+            let submit = ManageOfferRequest {
+                action: OfferAction::Submit,
+                offer: None,
+            };
+            let buyer = Party {
+                party_id: "BUYER_001".to_string(),
+                full_name: "Buyer".to_string(),
+                email: "buyer@example.com".to_string(),
+                mobile: String::new(),
+                address: PropertyAddress::default(),
+                wallet_address: accounts.alice,
+                signed_at: None,
+            };
+            assert_eq!(contract.add_buyer(buyer), Ok(()));
+
+            // Response events report the real outcome
+            let response = contract.manage_offer(submit.clone()).unwrap();
+            assert_eq!(response.error, Some(SaleError::OfferAmountRequired));
+            let generated = ink::env::test::recorded_events()
+                .filter_map(|event| {
+                    <ManageOfferResponseGenerated as scale::Decode>::decode(&mut &event.data[..]).ok()
+                })
+                .last()
+                .unwrap();
+            assert!(!generated.success);
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(
+                contract.set_failure_mode(FailureMode::Revert),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.set_failure_mode(FailureMode::Revert), Ok(()));
+            assert_eq!(contract.get_failure_mode(), FailureMode::Revert);
+            assert_eq!(
+                contract.manage_offer(submit),
+                Err(ContractError::Rejected(SaleError::OfferAmountRequired))
+            );
+            assert_eq!(
+                contract.sign_contract(SignContractRequest {}),
+                Err(ContractError::Rejected(SaleError::NoSellers))
+            );
+            let offer = ManageOfferRequest {
+                action: OfferAction::Submit,
+                offer: Some(gbp(640000)),
+            };
+            assert!(contract.manage_offer(offer).unwrap().success);
+        }
+
+        #[ink::test]
+        fn arbitrator_voids_signatures_and_reverts_offers() {
+            type Env = ink::env::DefaultEnvironment;
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = sale(Some(accepted_offer()), ContractStatus::UnderOffer);
+            let evidence = Hash::from([3; 32]);
+            let signature = DisputeSubject::Signature {
+                party_id: "BUYER_001".to_string(),
+            };
+
+            // Only signatures that exist can be disputed, and only by the parties
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(
+                contract.open_dispute(signature.clone(), evidence),
+                Err(ContractError::InvalidInput)
+            );
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert!(contract.sign_contract(SignContractRequest {}).unwrap().success);
+            ink::env::test::set_caller::<Env>(accounts.eve);
+            assert_eq!(
+                contract.open_dispute(signature.clone(), evidence),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let signature_dispute = contract.open_dispute(signature, evidence).unwrap();
+            assert_eq!(
+                contract.sign_contract(SignContractRequest {}),
+                Err(ContractError::DisputeOpen)
+            );
+
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(
+                contract.resolve_dispute(signature_dispute, DisputeOutcome::RevertOffer),
+                Err(ContractError::InvalidInput)
+            );
+            let outcome = DisputeOutcome::VoidSignature;
+            assert_eq!(contract.resolve_dispute(signature_dispute, outcome), Ok(()));
+            assert_eq!(contract.get_buyers()[0].signed_at, None);
+            assert_eq!(contract.get_status(), ContractStatus::UnderOffer);
+
+            // Disputing the offer also freezes offer changes until it is decided
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            let offer_dispute = contract.open_dispute(DisputeSubject::Offer, evidence).unwrap();
+            let cancel = ManageOfferRequest {
+                action: OfferAction::Cancel,
+                offer: None,
+            };
+            assert_eq!(contract.manage_offer(cancel), Err(ContractError::DisputeOpen));
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.set_offer(None), Err(ContractError::DisputeOpen));
+            assert_eq!(
+                contract.resolve_dispute(offer_dispute, DisputeOutcome::RevertOffer),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.django);
+            let outcome = DisputeOutcome::RevertOffer;
+            assert_eq!(contract.resolve_dispute(offer_dispute, outcome), Ok(()));
+            assert_eq!(contract.get_offer().unwrap().offer_status, OfferStatus::Pending);
+
+            assert!(contract.get_open_disputes().is_empty());
+            let history = contract.get_disputes(0, 10);
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[0].outcome, Some(DisputeOutcome::VoidSignature));
+            assert_eq!(history[1].opened_by, accounts.charlie);
+            let function_name = "resolve_dispute".to_string();
+            let resolved = contract.get_audit_log_by_function(function_name, 0, u64::MAX, None, 10);
+            assert_eq!(resolved.entries.len(), 2);
+
+            // The offer can only be disputed again once the cooldown has passed
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(
+                contract.open_dispute(DisputeSubject::Offer, evidence),
+                Err(ContractError::InvalidInput)
+            );
+            let resolved_at = history[1].resolved_at.unwrap();
+            ink::env::test::set_block_timestamp::<Env>(resolved_at + DISPUTE_COOLDOWN);
+            assert!(contract.open_dispute(DisputeSubject::Offer, evidence).is_ok());
+
+            // Dispute calls take their audit request ids from the request history's sequence
+            let request_ids: Vec<u64> = contract
+                .get_audit_log(0, contract.get_audit_log_count())
+                .iter()
+                .filter_map(|entry| match entry {
+                    AuditLogEntry::FunctionCall { request_id, .. } => Some(*request_id),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(request_ids, (0..6).collect::<Vec<_>>());
+            assert_eq!(contract.get_request_count(), 6);
+        }
     }
 }
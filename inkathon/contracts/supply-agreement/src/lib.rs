@@ -0,0 +1,1862 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod supplyagreement {
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::call::{build_call, ExecutionInput, FromAccountId, Selector};
+    use ink::prelude::format;
+    use ink::prelude::string::{String, ToString};
+    use ink::prelude::vec;
+    use ink::prelude::vec::Vec;
+    use ink::ToAccountId;
+    use latedeliveryandpenalty::{
+        Decimal, Duration, LateDeliveryAndPenaltyRef, LateDeliveryAndPenaltyRequest,
+        PenaltyLedger, Rounding, TemporalUnit,
+    };
+
+    // Error types
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ContractError {
+        Unauthorized,
+        ContractPaused,
+        InvalidInput,
+        TransferFailed,
+        ProcessingFailed,
+        /// The clause contract is for different parties or does not host the clause
+        ClauseMismatch,
+        /// A clause contract rejected a forwarded call
+        ClauseCallFailed,
+        NotDelivered,
+        PaymentIncomplete,
+        AlreadySettled,
+        /// A clause's penalty has grown since it was last assessed, so only the buyer may settle
+        PenaltyNotAssessed,
+    }
+
+    pub type Result<T> = core::result::Result<T, ContractError>;
+
+    /// Upper bound on linked clauses, each of which is called on receipt and settlement
+    pub const MAX_CLAUSES: u32 = 16;
+
+    /// Upper bound on audit log entries archived into one checkpoint
+    pub const MAX_AUDIT_ARCHIVE_BATCH: u64 = 256;
+
+    /// Clause administration messages the owner may call through `call_clause`. Anything
+    /// else could change the clause's terms or the roles the agreement relies on, such as
+    /// its attestor, or act on the parties' behalf.
+    const ADMIN_MESSAGES: [[u8; 4]; 9] = [
+        ink::selector_bytes!("pause"),
+        ink::selector_bytes!("unpause"),
+        ink::selector_bytes!("add_oracle"),
+        ink::selector_bytes!("remove_oracle"),
+        ink::selector_bytes!("set_oracle_threshold"),
+        ink::selector_bytes!("set_attestation_tolerance"),
+        ink::selector_bytes!("set_weekend"),
+        ink::selector_bytes!("add_holiday"),
+        ink::selector_bytes!("remove_holiday"),
+    ];
+
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct FieldChange {
+        pub field_name: String,
+        pub old_value: String,
+        pub new_value: String,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    #[repr(u8)]
+    pub enum AuditLogEntry {
+        FunctionCall {
+            caller: AccountId,
+            timestamp: u64,
+            function_name: String,
+            request_id: u64,
+            field_changes: Vec<FieldChange>,
+        } = 0,
+        DirectFieldChange {
+            field_name: String,
+            changed_by: AccountId,
+            old_value: String,
+            new_value: String,
+            block_number: u64,
+            timestamp: u64,
+        } = 1,
+    }
+
+    impl AuditLogEntry {
+        /// Account that made the call or the change
+        pub fn caller(&self) -> AccountId {
+            match self {
+                AuditLogEntry::FunctionCall { caller, .. } => *caller,
+                AuditLogEntry::DirectFieldChange { changed_by, .. } => *changed_by,
+            }
+        }
+
+        pub fn timestamp(&self) -> u64 {
+            match self {
+                AuditLogEntry::FunctionCall { timestamp, .. }
+                | AuditLogEntry::DirectFieldChange { timestamp, .. } => *timestamp,
+            }
+        }
+    }
+
+    /// A page of audit log entries with their log indexes, and the cursor for the next page
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct AuditLogPage {
+        pub entries: Vec<(u64, AuditLogEntry)>,
+        pub next_cursor: Option<u64>,
+    }
+
+    /// Which audit log entries may be archived and pruned; by default nothing is
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct AuditRetention {
+        /// Entries older than this many milliseconds
+        pub max_age: Option<u64>,
+        /// Entries beyond this many of the most recent
+        pub max_entries: Option<u64>,
+    }
+
+    /// Merkle root over archived entries `start..end`, kept after the entries are pruned
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct AuditCheckpoint {
+        pub start: u64,
+        pub end: u64,
+        pub root: Hash,
+        /// Hash chain value of the last archived entry
+        pub head: Hash,
+        pub archived_by: AccountId,
+        pub archived_at: u64,
+    }
+
+    /// A late delivery clause contract and the clause it hosts for this agreement
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ClauseLink {
+        pub contract: AccountId,
+        pub clause_id: String,
+        /// Deployed by this agreement, which is then the clause contract's owner
+        pub instantiated: bool,
+    }
+
+    /// Penalty ledgers of every linked clause added together
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PenaltySummary {
+        pub assessed: Balance,
+        pub paid: Balance,
+        pub written_off: Balance,
+        pub outstanding: Balance,
+    }
+
+    impl PenaltySummary {
+        fn add(&mut self, ledger: &PenaltyLedger) {
+            self.assessed = self.assessed.saturating_add(ledger.assessed);
+            self.paid = self.paid.saturating_add(ledger.paid);
+            self.written_off = self.written_off.saturating_add(ledger.written_off);
+            self.outstanding = self.outstanding.saturating_add(ledger.outstanding());
+        }
+    }
+
+    /// How the contract price was split when the agreement settled
+    #[derive(scale::Decode, scale::Encode, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Settlement {
+        pub settled_at: u64,
+        pub contract_price: Balance,
+        /// The clauses' penalty ledgers before settlement
+        pub penalties: PenaltySummary,
+        /// Outstanding penalties kept back from the seller and returned to the buyer, which
+        /// the clauses record as paid
+        pub withheld: Balance,
+        pub paid_to_seller: Balance,
+    }
+
+    /// Arguments a forwarded message was given already SCALE encoded, passed on as they are
+    struct EncodedInput(Vec<u8>);
+
+    impl scale::Encode for EncodedInput {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(&self.0);
+        }
+    }
+
+    /// Output of a forwarded message, returned still SCALE encoded
+    struct EncodedOutput(Vec<u8>);
+
+    impl scale::Decode for EncodedOutput {
+        fn decode<I: scale::Input>(input: &mut I) -> core::result::Result<Self, scale::Error> {
+            let mut output = vec![0; input.remaining_len()?.unwrap_or(0)];
+            input.read(&mut output)?;
+            Ok(Self(output))
+        }
+    }
+
+    // Events
+    #[ink(event)]
+    pub struct ContractCreated {
+        #[ink(topic)]
+        pub owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ContractPaused {
+        #[ink(topic)]
+        pub by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ContractUnpaused {
+        #[ink(topic)]
+        pub by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ClauseLinked {
+        #[ink(topic)]
+        pub contract: AccountId,
+        pub clause_id: String,
+        pub instantiated: bool,
+    }
+
+    #[ink(event)]
+    pub struct PaymentFunded {
+        #[ink(topic)]
+        pub buyer: AccountId,
+        pub amount: Balance,
+        pub total: Balance,
+    }
+
+    /// Dispatch was recorded here and forwarded to `clauses` clause contracts
+    #[ink(event)]
+    pub struct DeliveryDispatched {
+        #[ink(topic)]
+        pub seller: AccountId,
+        pub timestamp: u64,
+        pub clauses: u32,
+    }
+
+    /// Receipt was recorded here and forwarded to `clauses` clause contracts
+    #[ink(event)]
+    pub struct DeliveryReceived {
+        #[ink(topic)]
+        pub confirmed_by: AccountId,
+        pub timestamp: u64,
+        pub clauses: u32,
+    }
+
+    #[ink(event)]
+    pub struct PaymentSettled {
+        #[ink(topic)]
+        pub seller: AccountId,
+        pub paid_to_seller: Balance,
+        pub withheld: Balance,
+        pub penalties_assessed: Balance,
+    }
+
+    #[ink(event)]
+    pub struct FunctionCalled {
+        #[ink(topic)]
+        pub caller: AccountId,
+        #[ink(topic)]
+        pub function_name: String,
+        pub request_id: u64,
+        pub timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct AuditLogArchived {
+        #[ink(topic)]
+        pub checkpoint_id: u64,
+        pub start: u64,
+        pub end: u64,
+        pub root: Hash,
+    }
+
+    #[ink(event)]
+    pub struct ContractDataChanged {
+        #[ink(topic)]
+        pub field_name: String,
+        #[ink(topic)]
+        pub changed_by: AccountId,
+        pub old_value: String,
+        pub new_value: String,
+        pub block_number: u64,
+        pub timestamp: u64,
+    }
+
+    // Main contract storage
+    #[ink(storage)]
+    pub struct SupplyAgreement {
+        owner: AccountId,
+        buyer: AccountId,
+        seller: AccountId,
+        paused: bool,
+        contract_price: Balance,
+        funded: Balance,
+        clauses: Vec<ClauseLink>,
+        dispatched_at: Option<u64>,
+        received_at: Option<u64>,
+        settlement: Option<Settlement>,
+        request_count: u64,
+        audit_log: ink::storage::Mapping<u64, AuditLogEntry>,
+        audit_log_count: u64,
+        /// Running hash of each audit log entry, chained from the previous entry's hash
+        audit_log_hashes: ink::storage::Mapping<u64, Hash>,
+        audit_log_head: Hash,
+        /// Log indexes of each caller's entries, by position in that caller's history
+        audit_log_by_caller: ink::storage::Mapping<(AccountId, u64), u64>,
+        audit_log_caller_count: ink::storage::Mapping<AccountId, u64>,
+        audit_log_caller_start: ink::storage::Mapping<AccountId, u64>,
+        /// Log indexes of function call entries, by position in that function's history
+        audit_log_by_function: ink::storage::Mapping<(String, u64), u64>,
+        audit_log_function_count: ink::storage::Mapping<String, u64>,
+        audit_log_function_start: ink::storage::Mapping<String, u64>,
+        /// First entry not yet archived and pruned
+        audit_log_start: u64,
+        /// Hash chain value of the last pruned entry
+        audit_archived_head: Hash,
+        audit_retention: AuditRetention,
+        audit_checkpoints: ink::storage::Mapping<u64, AuditCheckpoint>,
+        audit_checkpoint_count: u64,
+        pending_field_changes: Vec<FieldChange>,
+    }
+
+    impl SupplyAgreement {
+        /// Agreement for goods worth `contract_price`, which the buyer pays into the contract
+        #[ink(constructor)]
+        pub fn new(buyer: AccountId, seller: AccountId, contract_price: Balance) -> Self {
+            let caller = Self::env().caller();
+
+            Self::env().emit_event(ContractCreated { owner: caller });
+
+            Self {
+                owner: caller,
+                buyer,
+                seller,
+                paused: false,
+                contract_price,
+                funded: 0,
+                clauses: Vec::new(),
+                dispatched_at: None,
+                received_at: None,
+                settlement: None,
+                request_count: 0,
+                audit_log: ink::storage::Mapping::default(),
+                audit_log_count: 0,
+                audit_log_hashes: ink::storage::Mapping::default(),
+                audit_log_head: Hash::default(),
+                audit_log_by_caller: ink::storage::Mapping::default(),
+                audit_log_caller_count: ink::storage::Mapping::default(),
+                audit_log_caller_start: ink::storage::Mapping::default(),
+                audit_log_by_function: ink::storage::Mapping::default(),
+                audit_log_function_count: ink::storage::Mapping::default(),
+                audit_log_function_start: ink::storage::Mapping::default(),
+                audit_log_start: 0,
+                audit_archived_head: Hash::default(),
+                audit_retention: AuditRetention::default(),
+                audit_checkpoints: ink::storage::Mapping::default(),
+                audit_checkpoint_count: 0,
+                pending_field_changes: Vec::new(),
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_owner(&self) -> AccountId {
+            self.owner
+        }
+
+        #[ink(message)]
+        pub fn get_buyer(&self) -> AccountId {
+            self.buyer
+        }
+
+        #[ink(message)]
+        pub fn get_seller(&self) -> AccountId {
+            self.seller
+        }
+
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            self.set_paused(true);
+            self.env().emit_event(ContractPaused { by: caller });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            self.set_paused(false);
+            self.env().emit_event(ContractUnpaused { by: caller });
+            Ok(())
+        }
+
+        fn set_paused(&mut self, paused: bool) {
+            let old_str = format!("{}", self.paused);
+            self.paused = paused;
+            self.log_field_change("paused", &old_str, &format!("{}", paused));
+            let request_id = self.next_request_id();
+            let function_name = if paused { "pause" } else { "unpause" };
+            self.log_function_call(function_name, request_id);
+        }
+
+        // === CLAUSES ===
+
+        /// Link a deployed clause contract. It must be between the same buyer and seller, host
+        /// `clause_id` and name this agreement as its attestor and settlement agent, so receipt
+        /// can be forwarded and withheld penalties recorded. A clause that has already been
+        /// received or terminated cannot be linked.
+        #[ink(message)]
+        pub fn add_clause(&mut self, contract: AccountId, clause_id: String) -> Result<()> {
+            self.check_can_link()?;
+            if self.clauses.iter().any(|link| link.contract == contract) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let clause: LateDeliveryAndPenaltyRef = FromAccountId::from_account_id(contract);
+            if clause.get_buyer() != self.buyer
+                || clause.get_seller() != self.seller
+                || clause.get_clause(clause_id.clone()).is_none()
+                || clause.get_attestor() != Some(self.env().account_id())
+                || clause.get_settlement_agent() != Some(self.env().account_id())
+            {
+                return Err(ContractError::ClauseMismatch);
+            }
+
+            let received = clause
+                .get_delivery(clause_id.clone())
+                .is_some_and(|delivery| delivery.received_at.is_some());
+            if received || clause.is_terminated(clause_id.clone()) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            self.link_clause(contract, clause_id, false);
+            let request_id = self.next_request_id();
+            self.log_function_call("add_clause", request_id);
+            Ok(())
+        }
+
+        /// Deploy a clause contract from uploaded `code_hash` between this agreement's parties,
+        /// with this agreement as its owner, attestor and settlement agent, and link it
+        #[ink(message)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn instantiate_clause(
+            &mut self,
+            code_hash: Hash,
+            arbitrator: AccountId,
+            agreed_delivery: u64,
+            clause_id: String,
+            force_majeure: bool,
+            penalty_duration: Duration,
+            penalty_percentage: Decimal,
+            cap_percentage: Decimal,
+            termination: Duration,
+            fractional_part: TemporalUnit,
+            goods_value: Decimal,
+        ) -> Result<AccountId> {
+            self.check_can_link()?;
+
+            let salt = (self.clauses.len() as u32).to_le_bytes();
+            let mut clause = LateDeliveryAndPenaltyRef::new(
+                self.buyer,
+                self.seller,
+                arbitrator,
+                agreed_delivery,
+                clause_id.clone(),
+                force_majeure,
+                penalty_duration,
+                penalty_percentage,
+                cap_percentage,
+                termination,
+                fractional_part,
+                goods_value,
+            )
+            .code_hash(code_hash)
+            .endowment(0)
+            .salt_bytes(salt)
+            .try_instantiate()
+            .map_err(|_| ContractError::ClauseCallFailed)?
+            .map_err(|_| ContractError::ClauseCallFailed)?
+            .map_err(|_| ContractError::InvalidInput)?;
+
+            let agreement = Some(self.env().account_id());
+            match clause.call_mut().set_attestor(agreement).try_invoke() {
+                Ok(Ok(Ok(()))) => {}
+                _ => return Err(ContractError::ClauseCallFailed),
+            }
+            match clause.call_mut().set_settlement_agent(agreement).try_invoke() {
+                Ok(Ok(Ok(()))) => {}
+                _ => return Err(ContractError::ClauseCallFailed),
+            }
+
+            let contract = clause.to_account_id();
+            self.link_clause(contract, clause_id, true);
+            let request_id = self.next_request_id();
+            self.log_function_call("instantiate_clause", request_id);
+            Ok(contract)
+        }
+
+        /// Owner calls an administration message on a clause contract this agreement deployed,
+        /// and so owns, such as `pause` or `add_oracle`. `input` is the message's SCALE encoded
+        /// arguments.
+        /// Returns the message's SCALE encoded output, including any error it returned.
+        #[ink(message)]
+        pub fn call_clause(
+            &mut self,
+            contract: AccountId,
+            selector: [u8; 4],
+            input: Vec<u8>,
+        ) -> Result<Vec<u8>> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let owned = self
+                .clauses
+                .iter()
+                .any(|link| link.contract == contract && link.instantiated);
+            if !owned || !ADMIN_MESSAGES.contains(&selector) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let input = ExecutionInput::new(Selector::new(selector)).push_arg(EncodedInput(input));
+            let output = build_call::<ink::env::DefaultEnvironment>()
+                .call(contract)
+                .exec_input(input)
+                .returns::<EncodedOutput>()
+                .try_invoke();
+            let output = match output {
+                Ok(Ok(output)) => output.0,
+                _ => return Err(ContractError::ClauseCallFailed),
+            };
+
+            let request_id = self.next_request_id();
+            self.log_function_call("call_clause", request_id);
+            Ok(output)
+        }
+
+        #[ink(message)]
+        pub fn get_clauses(&self) -> Vec<ClauseLink> {
+            self.clauses.clone()
+        }
+
+        fn check_can_link(&self) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            if self.env().caller() != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.received_at.is_some() || self.clauses.len() as u32 >= MAX_CLAUSES {
+                return Err(ContractError::InvalidInput);
+            }
+            Ok(())
+        }
+
+        fn link_clause(&mut self, contract: AccountId, clause_id: String, instantiated: bool) {
+            let link = ClauseLink {
+                contract,
+                clause_id: clause_id.clone(),
+                instantiated,
+            };
+            self.log_field_change("clauses", "", &format!("{:?}", link));
+            self.clauses.push(link);
+            self.env().emit_event(ClauseLinked {
+                contract,
+                clause_id,
+                instantiated,
+            });
+        }
+
+        // === DELIVERY ===
+
+        /// Seller records dispatch of the goods, which every linked clause not yet dispatched,
+        /// received or terminated records at the same block time. Fails without recording
+        /// anything if any clause rejects it.
+        #[ink(message)]
+        pub fn record_dispatch(&mut self) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.seller {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.dispatched_at.is_some() || self.received_at.is_some() {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let mut forwarded = 0u32;
+            for link in &self.clauses {
+                let mut clause: LateDeliveryAndPenaltyRef =
+                    FromAccountId::from_account_id(link.contract);
+                let pending = clause.get_delivery(link.clause_id.clone()).is_some_and(|delivery| {
+                    delivery.dispatched_at.is_none() && delivery.received_at.is_none()
+                });
+                if !pending || clause.is_terminated(link.clause_id.clone()) {
+                    continue;
+                }
+                match clause.call_mut().record_dispatch(link.clause_id.clone()).try_invoke() {
+                    Ok(Ok(Ok(()))) => forwarded = forwarded.saturating_add(1),
+                    _ => return Err(ContractError::ClauseCallFailed),
+                }
+            }
+
+            let timestamp = self.env().block_timestamp();
+            self.log_field_change("dispatched_at", "None", &format!("{:?}", Some(timestamp)));
+            self.dispatched_at = Some(timestamp);
+            self.env().emit_event(DeliveryDispatched {
+                seller: caller,
+                timestamp,
+                clauses: forwarded,
+            });
+            let request_id = self.next_request_id();
+            self.log_function_call("record_dispatch", request_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_dispatched_at(&self) -> Option<u64> {
+            self.dispatched_at
+        }
+
+        /// Buyer records receipt of the goods, which every linked clause not yet received or
+        /// terminated records at the same block time. Fails without recording anything if any
+        /// clause rejects it.
+        #[ink(message)]
+        pub fn confirm_receipt(&mut self) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.buyer {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.received_at.is_some() {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let mut forwarded = 0u32;
+            for link in &self.clauses {
+                let mut clause: LateDeliveryAndPenaltyRef =
+                    FromAccountId::from_account_id(link.contract);
+                let received = clause
+                    .get_delivery(link.clause_id.clone())
+                    .is_some_and(|delivery| delivery.received_at.is_some());
+                if received || clause.is_terminated(link.clause_id.clone()) {
+                    continue;
+                }
+                match clause.call_mut().confirm_receipt(link.clause_id.clone()).try_invoke() {
+                    Ok(Ok(Ok(()))) => forwarded = forwarded.saturating_add(1),
+                    _ => return Err(ContractError::ClauseCallFailed),
+                }
+            }
+
+            let timestamp = self.env().block_timestamp();
+            self.log_field_change("received_at", "None", &format!("{:?}", Some(timestamp)));
+            self.received_at = Some(timestamp);
+            self.env().emit_event(DeliveryReceived {
+                confirmed_by: caller,
+                timestamp,
+                clauses: forwarded,
+            });
+            let request_id = self.next_request_id();
+            self.log_function_call("confirm_receipt", request_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_received_at(&self) -> Option<u64> {
+            self.received_at
+        }
+
+        // === SETTLEMENT ===
+
+        /// Buyer pays all or part of the contract price into the agreement
+        #[ink(message, payable)]
+        pub fn fund_payment(&mut self) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let buyer = self.env().caller();
+            if buyer != self.buyer {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let amount = self.env().transferred_value();
+            let total = self.funded.saturating_add(amount);
+            if amount == 0 || total > self.contract_price || self.settlement.is_some() {
+                return Err(ContractError::InvalidInput);
+            }
+
+            self.log_field_change("funded", &format!("{}", self.funded), &format!("{}", total));
+            self.funded = total;
+            self.env().emit_event(PaymentFunded {
+                buyer,
+                amount,
+                total,
+            });
+            let request_id = self.next_request_id();
+            self.log_function_call("fund_payment", request_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_funded(&self) -> Balance {
+            self.funded
+        }
+
+        #[ink(message)]
+        pub fn get_contract_price(&self) -> Balance {
+            self.contract_price
+        }
+
+        /// Penalty ledgers of all linked clauses, as they stand now
+        #[ink(message)]
+        pub fn get_penalty_summary(&self) -> PenaltySummary {
+            let mut summary = PenaltySummary::default();
+            for ledger in self.clause_ledgers() {
+                summary.add(&ledger);
+            }
+            summary
+        }
+
+        fn clause_ledgers(&self) -> Vec<PenaltyLedger> {
+            self.clauses
+                .iter()
+                .map(|link| {
+                    let clause: LateDeliveryAndPenaltyRef =
+                        FromAccountId::from_account_id(link.contract);
+                    clause.get_penalty_ledger(link.clause_id.clone())
+                })
+                .collect()
+        }
+
+        /// Buyer or seller settles the price once the goods are received and the price is
+        /// funded. Penalties still outstanding under the clauses are withheld from the seller,
+        /// returned to the buyer and recorded as paid by the clauses; those already paid by
+        /// bond or instalment are not. The seller can only settle once every clause that is
+        /// not terminated has been assessed for its current penalty.
+        #[ink(message)]
+        pub fn settle(&mut self) -> Result<Settlement> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.buyer && caller != self.seller {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if self.settlement.is_some() {
+                return Err(ContractError::AlreadySettled);
+            }
+
+            if self.received_at.is_none() {
+                return Err(ContractError::NotDelivered);
+            }
+
+            if self.funded < self.contract_price {
+                return Err(ContractError::PaymentIncomplete);
+            }
+
+            let ledgers = self.clause_ledgers();
+            if caller == self.seller {
+                for (link, ledger) in self.clauses.iter().zip(&ledgers) {
+                    if self.penalty_unassessed(link, ledger)? {
+                        return Err(ContractError::PenaltyNotAssessed);
+                    }
+                }
+            }
+
+            let mut penalties = PenaltySummary::default();
+            for ledger in &ledgers {
+                penalties.add(ledger);
+            }
+            let withheld = penalties.outstanding.min(self.contract_price);
+            let mut remaining = withheld;
+            for (link, ledger) in self.clauses.iter().zip(&ledgers) {
+                let amount = remaining.min(ledger.outstanding());
+                if amount == 0 {
+                    continue;
+                }
+                let mut clause: LateDeliveryAndPenaltyRef =
+                    FromAccountId::from_account_id(link.contract);
+                let clause_id = link.clause_id.clone();
+                match clause.call_mut().record_penalty_settlement(clause_id, amount).try_invoke() {
+                    Ok(Ok(Ok(()))) => remaining = remaining.saturating_sub(amount),
+                    _ => return Err(ContractError::ClauseCallFailed),
+                }
+            }
+
+            let paid_to_seller = self.contract_price.saturating_sub(withheld);
+            if paid_to_seller > 0 {
+                self.env()
+                    .transfer(self.seller, paid_to_seller)
+                    .map_err(|_| ContractError::TransferFailed)?;
+            }
+            if withheld > 0 {
+                self.env()
+                    .transfer(self.buyer, withheld)
+                    .map_err(|_| ContractError::TransferFailed)?;
+            }
+
+            let settlement = Settlement {
+                settled_at: self.env().block_timestamp(),
+                contract_price: self.contract_price,
+                penalties,
+                withheld,
+                paid_to_seller,
+            };
+            self.env().emit_event(PaymentSettled {
+                seller: self.seller,
+                paid_to_seller,
+                withheld,
+                penalties_assessed: settlement.penalties.assessed,
+            });
+            self.log_field_change("settlement", "None", &format!("{:?}", settlement));
+            self.settlement = Some(settlement.clone());
+            let request_id = self.next_request_id();
+            self.log_function_call("settle", request_id);
+            Ok(settlement)
+        }
+
+        /// Whether a clause's penalty, evaluated now, is more than has been assessed
+        fn penalty_unassessed(&self, link: &ClauseLink, ledger: &PenaltyLedger) -> Result<bool> {
+            let clause: LateDeliveryAndPenaltyRef = FromAccountId::from_account_id(link.contract);
+            if clause.is_terminated(link.clause_id.clone()) {
+                return Ok(false);
+            }
+
+            let request = LateDeliveryAndPenaltyRequest::default();
+            let preview = clause
+                .preview_penalty(link.clause_id.clone(), request)
+                .map_err(|_| ContractError::ClauseCallFailed)?;
+            let penalty = preview
+                .response
+                .penalty
+                .to_integer(Rounding::Down)
+                .ok_or(ContractError::ClauseCallFailed)?;
+            Ok(penalty > ledger.net_assessed())
+        }
+
+        #[ink(message)]
+        pub fn get_settlement(&self) -> Option<Settlement> {
+            self.settlement.clone()
+        }
+
+        // === AUDIT LOG ===
+
+        /// Allocate the next request id; ids are never reused, even within a block
+        fn next_request_id(&mut self) -> u64 {
+            let request_id = self.request_count;
+            self.request_count = self.request_count.saturating_add(1);
+            request_id
+        }
+
+        #[ink(message)]
+        pub fn get_request_count(&self) -> u64 {
+            self.request_count
+        }
+
+        /// Record a function call in the audit log, including any pending field changes
+        fn log_function_call(&mut self, function_name: &str, request_id: u64) {
+            let caller = self.env().caller();
+            let timestamp = self.env().block_timestamp();
+
+            // Take all pending field changes and include them in this function call entry
+            let field_changes = core::mem::take(&mut self.pending_field_changes);
+
+            let log_entry = AuditLogEntry::FunctionCall {
+                caller,
+                timestamp,
+                function_name: function_name.to_string(),
+                request_id,
+                field_changes: field_changes.clone(),
+            };
+
+            self.append_audit_entry(&log_entry);
+
+            self.env().emit_event(FunctionCalled {
+                caller,
+                function_name: function_name.to_string(),
+                request_id,
+                timestamp,
+            });
+
+            // Emit individual field change events for each change
+            for field_change in field_changes {
+                self.env().emit_event(ContractDataChanged {
+                    field_name: field_change.field_name,
+                    changed_by: caller,
+                    old_value: field_change.old_value,
+                    new_value: field_change.new_value,
+                    block_number: self.env().block_number() as u64,
+                    timestamp,
+                });
+            }
+        }
+
+        /// Record a field change - adds to pending changes for inclusion in next function call log
+        fn log_field_change(&mut self, field_name: &str, old_value: &str, new_value: &str) {
+            let field_change = FieldChange {
+                field_name: field_name.to_string(),
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+            };
+
+            self.pending_field_changes.push(field_change);
+        }
+
+        /// Record a direct field change immediately (for setter functions called directly)
+        fn log_direct_field_change(&mut self, field_name: &str, old_value: &str, new_value: &str) {
+            let caller = self.env().caller();
+            let timestamp = self.env().block_timestamp();
+            let block_number = self.env().block_number() as u64;
+
+            let log_entry = AuditLogEntry::DirectFieldChange {
+                field_name: field_name.to_string(),
+                changed_by: caller,
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+                block_number,
+                timestamp,
+            };
+
+            self.append_audit_entry(&log_entry);
+
+            // Emit event
+            self.env().emit_event(ContractDataChanged {
+                field_name: field_name.to_string(),
+                changed_by: caller,
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+                block_number,
+                timestamp,
+            });
+        }
+
+        /// Store an entry at the next index, extend the hash chain over it and index it
+        fn append_audit_entry(&mut self, log_entry: &AuditLogEntry) {
+            let index = self.audit_log_count;
+            let hash = Self::chain_audit_hash(&self.audit_log_head, log_entry);
+
+            // Store with current count as index, then increment
+            self.audit_log.insert(index, log_entry);
+            self.audit_log_hashes.insert(index, &hash);
+            self.audit_log_head = hash;
+            self.audit_log_count = self.audit_log_count.saturating_add(1);
+
+            let caller = log_entry.caller();
+            let position = self.audit_log_caller_count.get(caller).unwrap_or(0);
+            self.audit_log_by_caller.insert((caller, position), &index);
+            self.audit_log_caller_count.insert(caller, &position.saturating_add(1));
+
+            if let AuditLogEntry::FunctionCall { function_name, .. } = log_entry {
+                let position = self.audit_log_function_count.get(function_name).unwrap_or(0);
+                self.audit_log_by_function.insert((function_name.clone(), position), &index);
+                self.audit_log_function_count.insert(function_name, &position.saturating_add(1));
+            }
+        }
+
+        /// Blake2x256 over the previous hash followed by the SCALE-encoded entry
+        fn chain_audit_hash(previous: &Hash, log_entry: &AuditLogEntry) -> Hash {
+            let mut input = Vec::from(previous.as_ref());
+            scale::Encode::encode_to(log_entry, &mut input);
+            Self::blake2x256(&input)
+        }
+
+        fn blake2x256(input: &[u8]) -> Hash {
+            let mut output = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(input, &mut output);
+            Hash::from(output)
+        }
+
+        /// Hash of the latest audit log entry; all zeroes while the log is empty
+        #[ink(message)]
+        pub fn get_audit_log_head(&self) -> Hash {
+            self.audit_log_head
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_hash(&self, index: u64) -> Option<Hash> {
+            self.audit_log_hashes.get(index)
+        }
+
+        /// Recompute the hash chain over entries `start..end` and check it against the stored
+        /// hashes. Returns false if the range is out of bounds or any entry is missing or altered.
+        #[ink(message)]
+        pub fn verify_audit_range(&self, start: u64, end: u64) -> bool {
+            if start < self.audit_log_start || start > end || end > self.audit_log_count {
+                return false;
+            }
+
+            let mut hash = if start == 0 {
+                Hash::default()
+            } else if start == self.audit_log_start {
+                self.audit_archived_head
+            } else {
+                match self.audit_log_hashes.get(start - 1) {
+                    Some(hash) => hash,
+                    None => return false,
+                }
+            };
+
+            for i in start..end {
+                let Some(entry) = self.audit_log.get(i) else {
+                    return false;
+                };
+                hash = Self::chain_audit_hash(&hash, &entry);
+                if self.audit_log_hashes.get(i) != Some(hash) {
+                    return false;
+                }
+            }
+
+            true
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_count(&self) -> u64 {
+            self.audit_log_count
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log(&self, start: u64, limit: u64) -> Vec<AuditLogEntry> {
+            let mut entries = Vec::new();
+            let end = start.saturating_add(limit).min(self.audit_log_count);
+
+            for i in start..end {
+                if let Some(entry) = self.audit_log.get(i) {
+                    entries.push(entry);
+                }
+            }
+
+            entries
+        }
+
+        /// Entries made by `caller` with timestamps in `from..to`.
+        ///
+        /// Pass `None` as the cursor for the first page, then the returned `next_cursor`.
+        #[ink(message)]
+        pub fn get_audit_log_by_caller(
+            &self,
+            caller: AccountId,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let first = self.audit_log_caller_start.get(caller).unwrap_or(0);
+            let len = self.audit_log_caller_count.get(caller).unwrap_or(0);
+            self.audit_log_page(
+                first..len,
+                |position| self.audit_log_by_caller.get((caller, position)),
+                from,
+                to,
+                cursor,
+                limit,
+            )
+        }
+
+        /// Calls of `function_name` with timestamps in `from..to`, paginated as
+        /// `get_audit_log_by_caller`
+        #[ink(message)]
+        pub fn get_audit_log_by_function(
+            &self,
+            function_name: String,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let first = self.audit_log_function_start.get(&function_name).unwrap_or(0);
+            let len = self.audit_log_function_count.get(&function_name).unwrap_or(0);
+            self.audit_log_page(
+                first..len,
+                |position| self.audit_log_by_function.get((function_name.clone(), position)),
+                from,
+                to,
+                cursor,
+                limit,
+            )
+        }
+
+        /// All entries with timestamps in `from..to`, paginated as `get_audit_log_by_caller`
+        #[ink(message)]
+        pub fn get_audit_log_by_time(
+            &self,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let positions = self.audit_log_start..self.audit_log_count;
+            self.audit_log_page(positions, Some, from, to, cursor, limit)
+        }
+
+        /// Page through the retained `positions` of an index, where `log_index` maps a position
+        /// to its audit log index.
+        ///
+        /// Entries are appended in block order, so timestamps never decrease along the log or
+        /// any index into it. The first page binary searches for `from` and every page stops
+        /// at `to`, so only the returned entries and a logarithmic number of others are read.
+        fn audit_log_page(
+            &self,
+            positions: core::ops::Range<u64>,
+            log_index: impl Fn(u64) -> Option<u64>,
+            from: u64,
+            to: u64,
+            cursor: Option<u64>,
+            limit: u64,
+        ) -> AuditLogPage {
+            let timestamp_at = |position: u64| {
+                log_index(position)
+                    .and_then(|index| self.audit_log.get(index))
+                    .map(|entry| entry.timestamp())
+            };
+
+            let len = positions.end;
+            let mut position = match cursor {
+                Some(cursor) => cursor.max(positions.start),
+                None => {
+                    let (mut low, mut high) = (positions.start, len);
+                    while low < high {
+                        let mid = low + (high - low) / 2;
+                        if timestamp_at(mid).unwrap_or(0) < from {
+                            low = mid + 1;
+                        } else {
+                            high = mid;
+                        }
+                    }
+                    low
+                }
+            };
+
+            let mut entries = Vec::new();
+            while position < len {
+                if entries.len() as u64 >= limit {
+                    return AuditLogPage {
+                        entries,
+                        next_cursor: Some(position),
+                    };
+                }
+                if let Some(index) = log_index(position) {
+                    if let Some(entry) = self.audit_log.get(index) {
+                        if entry.timestamp() >= to {
+                            break;
+                        }
+                        entries.push((index, entry));
+                    }
+                }
+                position = position.saturating_add(1);
+            }
+
+            AuditLogPage {
+                entries,
+                next_cursor: None,
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_function_calls(&self, start: u64, limit: u64) -> Vec<AuditLogEntry> {
+            let mut entries = Vec::new();
+            let mut count = 0u64;
+
+            for i in start..self.audit_log_count {
+                if count >= limit {
+                    break;
+                }
+                if let Some(entry) = self.audit_log.get(i) {
+                    if matches!(entry, AuditLogEntry::FunctionCall { .. }) {
+                        entries.push(entry);
+                        count = count.saturating_add(1);
+                    }
+                }
+            }
+
+            entries
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_field_changes(&self, start: u64, limit: u64) -> Vec<AuditLogEntry> {
+            let mut entries = Vec::new();
+            let mut count = 0u64;
+
+            for i in start..self.audit_log_count {
+                if count >= limit {
+                    break;
+                }
+                if let Some(entry) = self.audit_log.get(i) {
+                    match entry {
+                        AuditLogEntry::DirectFieldChange { .. } => {
+                            entries.push(entry);
+                            count = count.saturating_add(1);
+                        }
+                        AuditLogEntry::FunctionCall {
+                            ref field_changes, ..
+                        } => {
+                            if !field_changes.is_empty() {
+                                entries.push(entry);
+                                count = count.saturating_add(1);
+                            }
+                        }
+                    }
+                }
+            }
+
+            entries
+        }
+
+        #[ink(message)]
+        pub fn get_audit_log_field_changes_by_field(
+            &self,
+            field_name: String,
+        ) -> Vec<AuditLogEntry> {
+            let mut matching_entries = Vec::new();
+
+            for i in 0..self.audit_log_count {
+                if let Some(entry) = self.audit_log.get(i) {
+                    match entry {
+                        AuditLogEntry::DirectFieldChange {
+                            field_name: ref entry_field_name,
+                            ..
+                        } => {
+                            if entry_field_name == &field_name {
+                                matching_entries.push(entry);
+                            }
+                        }
+                        AuditLogEntry::FunctionCall {
+                            ref field_changes, ..
+                        } => {
+                            for field_change in field_changes {
+                                if field_change.field_name == field_name {
+                                    matching_entries.push(entry.clone());
+                                    break; // Only add the entry once even if multiple matching fields
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            matching_entries
+        }
+
+        // === AUDIT LOG RETENTION ===
+
+        #[ink(message)]
+        pub fn set_audit_retention(&mut self, new_value: AuditRetention) -> Result<()> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            if new_value.max_entries == Some(0) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let old_value = format!("{:?}", self.audit_retention);
+            let new_value_str = format!("{:?}", new_value);
+            self.log_direct_field_change("audit_retention", &old_value, &new_value_str);
+            self.audit_retention = new_value;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_audit_retention(&self) -> AuditRetention {
+            self.audit_retention.clone()
+        }
+
+        /// Archive up to `limit` of the oldest entries the retention policy allows to be
+        /// pruned: commit them to a Merkle root checkpoint, then remove them from storage.
+        ///
+        /// Returns the number of entries archived. Nothing is archived when the policy
+        /// retains every entry.
+        #[ink(message)]
+        pub fn archive_audit_log(&mut self, limit: u64) -> Result<u64> {
+            if self.paused {
+                return Err(ContractError::ContractPaused);
+            }
+
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(ContractError::Unauthorized);
+            }
+
+            let start = self.audit_log_start;
+            let now = self.env().block_timestamp();
+            let max_end = start
+                .saturating_add(limit.min(MAX_AUDIT_ARCHIVE_BATCH))
+                .min(self.audit_log_count);
+
+            // Entries beyond the retained count, and then entries past the maximum age; both
+            // form a prefix of the log because timestamps never decrease
+            let mut end = match self.audit_retention.max_entries {
+                Some(max_entries) => self.audit_log_count.saturating_sub(max_entries),
+                None => 0,
+            }
+            .clamp(start, max_end);
+            if let Some(max_age) = self.audit_retention.max_age {
+                while end < max_end {
+                    match self.audit_log.get(end) {
+                        Some(entry) if now.saturating_sub(entry.timestamp()) > max_age => {
+                            end += 1
+                        }
+                        _ => break,
+                    }
+                }
+            }
+            if end == start {
+                return Ok(0);
+            }
+
+            let mut leaves = Vec::new();
+            for index in start..end {
+                let entry = self.audit_log.take(index).ok_or(ContractError::ProcessingFailed)?;
+                leaves.push(Self::audit_leaf_hash(index, &entry));
+                self.prune_audit_indexes(&entry);
+            }
+            let head = self
+                .audit_log_hashes
+                .get(end - 1)
+                .ok_or(ContractError::ProcessingFailed)?;
+            for index in start..end {
+                self.audit_log_hashes.remove(index);
+            }
+
+            let checkpoint_id = self.audit_checkpoint_count;
+            let root = Self::audit_merkle_root(leaves);
+            let checkpoint = AuditCheckpoint {
+                start,
+                end,
+                root,
+                head,
+                archived_by: caller,
+                archived_at: now,
+            };
+            self.audit_checkpoints.insert(checkpoint_id, &checkpoint);
+            self.audit_checkpoint_count = self.audit_checkpoint_count.saturating_add(1);
+            self.audit_log_start = end;
+            self.audit_archived_head = head;
+
+            self.env().emit_event(AuditLogArchived {
+                checkpoint_id,
+                start,
+                end,
+                root,
+            });
+            Ok(end - start)
+        }
+
+        /// Drop a pruned entry's positions from the caller and function indexes. Entries are
+        /// pruned in log order, so each is the oldest retained position of its index.
+        fn prune_audit_indexes(&mut self, log_entry: &AuditLogEntry) {
+            let caller = log_entry.caller();
+            let position = self.audit_log_caller_start.get(caller).unwrap_or(0);
+            self.audit_log_by_caller.remove((caller, position));
+            self.audit_log_caller_start.insert(caller, &position.saturating_add(1));
+
+            if let AuditLogEntry::FunctionCall { function_name, .. } = log_entry {
+                let position = self.audit_log_function_start.get(function_name).unwrap_or(0);
+                self.audit_log_by_function.remove((function_name.clone(), position));
+                self.audit_log_function_start.insert(function_name, &position.saturating_add(1));
+            }
+        }
+
+        /// Merkle leaf for an archived entry: Blake2x256 over `0x00` and the SCALE-encoded
+        /// `(index, entry)`
+        fn audit_leaf_hash(index: u64, log_entry: &AuditLogEntry) -> Hash {
+            let mut input = Vec::from([0u8]);
+            scale::Encode::encode_to(&(index, log_entry), &mut input);
+            Self::blake2x256(&input)
+        }
+
+        /// Merkle node: Blake2x256 over `0x01` and both children
+        fn audit_node_hash(left: &Hash, right: &Hash) -> Hash {
+            let mut input = Vec::from([1u8]);
+            input.extend_from_slice(left.as_ref());
+            input.extend_from_slice(right.as_ref());
+            Self::blake2x256(&input)
+        }
+
+        /// Root over `leaves`, pairing neighbours level by level and carrying an unpaired
+        /// last node up unchanged
+        fn audit_merkle_root(mut level: Vec<Hash>) -> Hash {
+            while level.len() > 1 {
+                level = level
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [left, right] => Self::audit_node_hash(left, right),
+                        _ => pair[0],
+                    })
+                    .collect();
+            }
+            level.first().copied().unwrap_or_default()
+        }
+
+        /// Check that `entry` was archived at `index` under a checkpoint. `proof` lists the
+        /// sibling hashes from the leaf up to the root, skipping levels where the node is
+        /// carried up unpaired.
+        #[ink(message)]
+        pub fn verify_audit_proof(
+            &self,
+            checkpoint_id: u64,
+            index: u64,
+            entry: AuditLogEntry,
+            proof: Vec<Hash>,
+        ) -> bool {
+            let Some(checkpoint) = self.audit_checkpoints.get(checkpoint_id) else {
+                return false;
+            };
+            if index < checkpoint.start || index >= checkpoint.end {
+                return false;
+            }
+
+            let mut position = index - checkpoint.start;
+            let mut width = checkpoint.end - checkpoint.start;
+            let mut hash = Self::audit_leaf_hash(index, &entry);
+            let mut siblings = proof.iter();
+            while width > 1 {
+                if position % 2 == 1 {
+                    let Some(sibling) = siblings.next() else {
+                        return false;
+                    };
+                    hash = Self::audit_node_hash(sibling, &hash);
+                } else if position + 1 < width {
+                    let Some(sibling) = siblings.next() else {
+                        return false;
+                    };
+                    hash = Self::audit_node_hash(&hash, sibling);
+                }
+                position /= 2;
+                width = width.div_ceil(2);
+            }
+
+            siblings.next().is_none() && hash == checkpoint.root
+        }
+
+        #[ink(message)]
+        pub fn get_audit_checkpoint(&self, checkpoint_id: u64) -> Option<AuditCheckpoint> {
+            self.audit_checkpoints.get(checkpoint_id)
+        }
+
+        #[ink(message)]
+        pub fn get_audit_checkpoint_count(&self) -> u64 {
+            self.audit_checkpoint_count
+        }
+
+        /// Index of the oldest entry still in storage
+        #[ink(message)]
+        pub fn get_audit_log_start(&self) -> u64 {
+            self.audit_log_start
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        type Env = ink::env::DefaultEnvironment;
+
+        const PRICE: Balance = 1_000_000;
+
+        fn balance_of(account: AccountId) -> Balance {
+            ink::env::test::get_account_balance::<Env>(account).unwrap_or_default()
+        }
+
+        /// Agreement owned by alice between charlie (buyer) and bob (seller)
+        fn agreement() -> SupplyAgreement {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            SupplyAgreement::new(accounts.charlie, accounts.bob, PRICE)
+        }
+
+        fn fund(contract: &mut SupplyAgreement, amount: Balance) -> Result<()> {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::transfer_in::<Env>(amount);
+            let result = contract.fund_payment();
+            ink::env::test::set_value_transferred::<Env>(0);
+            result
+        }
+
+        #[ink::test]
+        fn new_works() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let contract = agreement();
+            assert_eq!(contract.get_owner(), accounts.alice);
+            assert_eq!(contract.get_buyer(), accounts.charlie);
+            assert_eq!(contract.get_seller(), accounts.bob);
+            assert_eq!(contract.get_contract_price(), PRICE);
+            assert!(contract.get_clauses().is_empty());
+            assert_eq!(contract.get_penalty_summary(), PenaltySummary::default());
+            assert_eq!(contract.get_audit_log_count(), 0);
+        }
+
+        #[ink::test]
+        fn settlement_requires_receipt_and_full_payment() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = agreement();
+            let seller_balance = balance_of(accounts.bob);
+
+            // Only the buyer funds, and never beyond the price
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.fund_payment(), Err(ContractError::Unauthorized));
+            assert_eq!(fund(&mut contract, 600_000), Ok(()));
+            assert_eq!(fund(&mut contract, 400_001), Err(ContractError::InvalidInput));
+
+            // Only the seller records dispatch, and only once
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            assert_eq!(contract.record_dispatch(), Err(ContractError::Unauthorized));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            ink::env::test::set_block_timestamp::<Env>(21);
+            assert_eq!(contract.record_dispatch(), Ok(()));
+            assert_eq!(contract.get_dispatched_at(), Some(21));
+            assert_eq!(contract.record_dispatch(), Err(ContractError::InvalidInput));
+
+            assert_eq!(contract.settle(), Err(ContractError::NotDelivered));
+            assert_eq!(contract.confirm_receipt(), Err(ContractError::Unauthorized));
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(42);
+            assert_eq!(contract.confirm_receipt(), Ok(()));
+            assert_eq!(contract.get_received_at(), Some(42));
+            assert_eq!(contract.confirm_receipt(), Err(ContractError::InvalidInput));
+            assert_eq!(contract.settle(), Err(ContractError::PaymentIncomplete));
+
+            // Clauses cannot be linked once the goods have arrived
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(
+                contract.add_clause(accounts.django, String::from("clause")),
+                Err(ContractError::InvalidInput)
+            );
+
+            assert_eq!(fund(&mut contract, 400_000), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let settlement = contract.settle().unwrap();
+            assert_eq!(settlement.settled_at, 42);
+            assert_eq!((settlement.withheld, settlement.paid_to_seller), (0, PRICE));
+            assert_eq!(balance_of(accounts.bob), seller_balance + PRICE);
+            assert_eq!(contract.get_settlement(), Some(settlement));
+            assert_eq!(contract.settle(), Err(ContractError::AlreadySettled));
+        }
+
+        #[ink::test]
+        fn calls_are_audited() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = agreement();
+            assert_eq!(fund(&mut contract, PRICE), Ok(()));
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.pause(), Ok(()));
+            assert_eq!(contract.unpause(), Ok(()));
+
+            // Only instantiated clauses can be called through the agreement
+            assert_eq!(
+                contract.call_clause(accounts.django, ink::selector_bytes!("pause"), Vec::new()),
+                Err(ContractError::InvalidInput)
+            );
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(
+                contract.call_clause(accounts.django, ink::selector_bytes!("pause"), Vec::new()),
+                Err(ContractError::Unauthorized)
+            );
+
+            let entries = contract.get_audit_log(0, 10);
+            let calls: Vec<_> = entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    AuditLogEntry::FunctionCall { function_name, .. } => {
+                        Some(function_name.as_str())
+                    }
+                    AuditLogEntry::DirectFieldChange { .. } => None,
+                })
+                .collect();
+            assert_eq!(calls, ["fund_payment", "pause", "unpause"]);
+            assert_eq!(
+                entries[0],
+                AuditLogEntry::FunctionCall {
+                    caller: accounts.charlie,
+                    timestamp: 0,
+                    function_name: String::from("fund_payment"),
+                    request_id: 0,
+                    field_changes: vec![FieldChange {
+                        field_name: String::from("funded"),
+                        old_value: String::from("0"),
+                        new_value: PRICE.to_string(),
+                    }],
+                }
+            );
+            assert_eq!(contract.get_request_count(), 3);
+            assert!(contract.verify_audit_range(0, 3));
+            assert_eq!(contract.get_audit_log_hash(2), Some(contract.get_audit_log_head()));
+
+            let page = contract.get_audit_log_by_caller(accounts.alice, 0, u64::MAX, None, 1);
+            assert_eq!(page.entries[0].0, 1);
+            assert_eq!(page.next_cursor, Some(1));
+            let function_name = String::from("unpause");
+            let page = contract.get_audit_log_by_function(function_name, 0, u64::MAX, None, 10);
+            assert_eq!(page.entries.len(), 1);
+        }
+
+        #[ink::test]
+        fn audit_log_is_archived_under_retention_policy() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = agreement();
+            assert_eq!(contract.pause(), Ok(()));
+            assert_eq!(contract.unpause(), Ok(()));
+            let entries = contract.get_audit_log(0, 10);
+
+            let keep_one = AuditRetention {
+                max_age: None,
+                max_entries: Some(1),
+            };
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(
+                contract.set_audit_retention(keep_one.clone()),
+                Err(ContractError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.set_audit_retention(keep_one), Ok(()));
+            assert_eq!(contract.archive_audit_log(10), Ok(2));
+            assert_eq!(contract.get_audit_log_start(), 2);
+            assert!(contract.verify_audit_range(2, 3));
+
+            let leaves: Vec<Hash> = entries
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| SupplyAgreement::audit_leaf_hash(i as u64, entry))
+                .collect();
+            assert!(contract.verify_audit_proof(0, 1, entries[1].clone(), vec![leaves[0]]));
+            assert!(!contract.verify_audit_proof(0, 1, entries[0].clone(), vec![leaves[0]]));
+        }
+
+        #[ink::test]
+        fn penalty_summary_adds_clause_ledgers() {
+            let mut summary = PenaltySummary::default();
+            for (assessed, paid, written_off) in [(300, 100, 50), (200, 0, 0)] {
+                summary.add(&PenaltyLedger {
+                    assessed,
+                    acknowledged: 0,
+                    paid,
+                    written_off,
+                    ..Default::default()
+                });
+            }
+            assert_eq!(
+                summary,
+                PenaltySummary {
+                    assessed: 500,
+                    paid: 100,
+                    written_off: 50,
+                    outstanding: 350,
+                }
+            );
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+        use latedeliveryandpenalty::latedeliveryandpenalty::LateDeliveryAndPenalty;
+
+        type E2EResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+        const PRICE: Balance = 1_000_000_000;
+
+        fn days(amount: u128) -> Duration {
+            Duration {
+                amount,
+                unit: TemporalUnit::Days,
+            }
+        }
+
+        /// A clause agreed to be delivered at the Unix epoch, so any receipt is late enough
+        /// to reach its 10% cap of 1000 goods value
+        macro_rules! instantiate_clause {
+            ($call_builder:expr, $code_hash:expr, $clause_id:expr) => {
+                $call_builder.instantiate_clause(
+                    $code_hash,
+                    ink_e2e::account_id(ink_e2e::AccountKeyring::Dave),
+                    0,
+                    String::from($clause_id),
+                    false,
+                    days(1),
+                    Decimal::from_integer(1).unwrap(),
+                    Decimal::from_integer(10).unwrap(),
+                    days(30),
+                    TemporalUnit::Days,
+                    Decimal::from_integer(1000).unwrap(),
+                )
+            };
+        }
+
+        #[ink_e2e::test]
+        async fn receipt_is_forwarded_and_penalties_withheld<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let buyer = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+            let seller = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+            let mut constructor = SupplyAgreementRef::new(buyer, seller, PRICE);
+            let agreement = client
+                .instantiate("supplyagreement", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("agreement instantiate failed");
+            let mut call_builder = agreement.call_builder::<SupplyAgreement>();
+            let code_hash = client
+                .upload("latedeliveryandpenalty", &ink_e2e::alice())
+                .submit()
+                .await
+                .expect("clause upload failed")
+                .code_hash;
+
+            // The agreement deploys and owns its clauses
+            let mut clauses = Vec::new();
+            for clause_id in ["po-1", "po-2"] {
+                let instantiate = instantiate_clause!(call_builder, code_hash, clause_id);
+                let result = client
+                    .call(&ink_e2e::alice(), &instantiate)
+                    .submit()
+                    .await
+                    .expect("instantiate_clause failed");
+                clauses.push(result.return_value().expect("clause rejected"));
+            }
+
+            // Only the owner can pause a clause through the agreement
+            let pause = call_builder.call_clause(clauses[1], ink::selector_bytes!("pause"), vec![]);
+            client.call(&ink_e2e::alice(), &pause).submit().await.expect("pause failed");
+            let mut clause = ink_e2e::create_call_builder::<LateDeliveryAndPenalty>(clauses[1]);
+            let is_paused = client.call(&ink_e2e::alice(), &clause.is_paused()).dry_run().await?;
+            assert!(is_paused.return_value());
+            let unpause = ink::selector_bytes!("unpause");
+            let unpause = call_builder.call_clause(clauses[1], unpause, vec![]);
+            client.call(&ink_e2e::alice(), &unpause).submit().await.expect("unpause failed");
+            let receipt = ink::selector_bytes!("confirm_receipt");
+            let input = scale::Encode::encode(&String::from("po-2"));
+            let forged = call_builder.call_clause(clauses[1], receipt, input);
+            let result = client.call(&ink_e2e::alice(), &forged).dry_run().await?;
+            assert_eq!(result.return_value(), Err(ContractError::InvalidInput));
+            let set_attestor = ink::selector_bytes!("set_attestor");
+            let input = scale::Encode::encode(&Option::<AccountId>::None);
+            let replaced = call_builder.call_clause(clauses[1], set_attestor, input);
+            let result = client.call(&ink_e2e::alice(), &replaced).dry_run().await?;
+            assert_eq!(result.return_value(), Err(ContractError::InvalidInput));
+
+            let fund = call_builder.fund_payment();
+            client
+                .call(&ink_e2e::charlie(), &fund)
+                .value(PRICE)
+                .submit()
+                .await
+                .expect("fund_payment failed");
+            let dispatch = call_builder.record_dispatch();
+            client.call(&ink_e2e::bob(), &dispatch).submit().await.expect("dispatch failed");
+
+            // The buyer confirms receipt once, for every clause
+            let confirm = clause.confirm_receipt(String::from("po-2"));
+            client.call(&ink_e2e::charlie(), &confirm).submit().await.expect("receipt failed");
+            let confirm = call_builder.confirm_receipt();
+            client.call(&ink_e2e::charlie(), &confirm).submit().await.expect("receipt failed");
+            let mut first = ink_e2e::create_call_builder::<LateDeliveryAndPenalty>(clauses[0]);
+            let delivery = client
+                .call(&ink_e2e::alice(), &first.get_delivery(String::from("po-1")))
+                .dry_run()
+                .await?
+                .return_value()
+                .expect("no delivery");
+            assert!(delivery.dispatched_at.is_some());
+            assert!(delivery.received_at.is_some());
+
+            // The seller cannot settle before the late penalty is assessed
+            let settle = call_builder.settle();
+            let result = client.call(&ink_e2e::bob(), &settle).dry_run().await?;
+            assert_eq!(result.return_value(), Err(ContractError::PenaltyNotAssessed));
+            for (clause, clause_id) in [(&mut first, "po-1"), (&mut clause, "po-2")] {
+                let request = LateDeliveryAndPenaltyRequest::default();
+                let claim = clause.late_delivery_and_penalty(String::from(clause_id), request);
+                client.call(&ink_e2e::charlie(), &claim).submit().await.expect("claim failed");
+            }
+
+            let result = client
+                .call(&ink_e2e::bob(), &settle)
+                .submit()
+                .await
+                .expect("settle failed")
+                .return_value()
+                .expect("settlement rejected");
+            assert_eq!(result.penalties.assessed, 200);
+            assert_eq!((result.withheld, result.paid_to_seller), (200, PRICE - 200));
+
+            // The clauses record what was withheld as paid
+            let ledger = client
+                .call(&ink_e2e::alice(), &first.get_penalty_ledger(String::from("po-1")))
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!((ledger.paid, ledger.outstanding()), (100, 0));
+            let log_count = call_builder.get_audit_log_count();
+            let log_count = client.call(&ink_e2e::alice(), &log_count).dry_run().await?;
+            assert_eq!(log_count.return_value(), 8);
+            Ok(())
+        }
+    }
+}
@@ -20,8 +20,8 @@ import { Button } from '@/components/ui/button'
 import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card'
 import { Form, FormControl, FormField, FormItem, FormLabel, FormMessage } from '@/components/ui/form'
 import { Input } from '@/components/ui/input'
-import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '@/components/ui/select'
 import { contractTxWithToast } from '@/utils/contract-tx-with-toast'
+import { fromContractDecimal, toContractDecimal } from '@/utils/decimal'
 import { truncateHash } from '@/utils/truncate-hash'
 
 // Types for draft service
@@ -36,25 +36,20 @@ interface GeneratedDocument {
   templateData?: any
 }
 
+// Variants of the contract's `DraftFormat` enum
+const DRAFT_FORMATS = ['Markdown', 'Pdf'] as const
+
 // Form schemas
 const requestDraftSchema = z.object({
   clauseId: z.string().min(1, 'Clause ID is required'),
-  forceMajeure: z.boolean().default(false),
-  penaltyAmount: z.number().min(1, 'Penalty duration must be at least 1'),
-  penaltyUnit: z.enum(['days', 'weeks', 'months']).default('days'),
-  penaltyPercentage: z.number().min(0.1).max(100, 'Penalty percentage must be between 0.1% and 100%'),
-  capPercentage: z.number().min(0.1).max(100, 'Cap percentage must be between 0.1% and 100%'),
-  terminationAmount: z.number().min(1, 'Termination period must be at least 1'),
-  terminationUnit: z.enum(['days', 'weeks', 'months']).default('days'),
-  fractionalPart: z.enum(['days', 'weeks', 'months']).default('days'),
-  outputFormat: z.enum(['md', 'pdf']).default('md'),
+  outputFormat: z.enum(DRAFT_FORMATS).default('Markdown'),
 })
 
 const processRequestSchema = z.object({
   forceMajeure: z.boolean().default(false),
   agreedDelivery: z.string().min(1, 'Agreed delivery date is required'),
   deliveredAt: z.string().optional(),
-  goodsValue: z.string().min(1, 'Goods value is required'),
+  goodsValue: z.string().regex(/^\d+(\.\d{1,18})?$/, 'Goods value must be a decimal number'),
 })
 
 type RequestDraftForm = z.infer<typeof requestDraftSchema>
@@ -138,15 +133,7 @@ export const LateDeliveryContractInteractions: FC = () => {
     resolver: zodResolver(requestDraftSchema),
     defaultValues: {
       clauseId: 'test-clause-1',
-      forceMajeure: false,
-      penaltyAmount: 3,
-      penaltyUnit: 'days',
-      penaltyPercentage: 10.5,
-      capPercentage: 55,
-      terminationAmount: 15,
-      terminationUnit: 'days',
-      fractionalPart: 'days',
-      outputFormat: 'md',
+      outputFormat: 'Markdown',
     },
   })
 
@@ -219,14 +206,6 @@ export const LateDeliveryContractInteractions: FC = () => {
   // Request draft
   const handleRequestDraft: SubmitHandler<RequestDraftForm> = async ({
     clauseId,
-    forceMajeure,
-    penaltyAmount,
-    penaltyUnit,
-    penaltyPercentage,
-    capPercentage,
-    terminationAmount,
-    terminationUnit,
-    fractionalPart,
     outputFormat
   }) => {
     if (!activeAccount || !contract || !activeSigner || !api) {
@@ -235,36 +214,13 @@ export const LateDeliveryContractInteractions: FC = () => {
     }
 
     try {
-      // Construct the template data JSON from form fields
-      const templateData = {
-        "$class": "io.clause.latedeliveryandpenalty@0.1.0.LateDeliveryAndPenalty",
-        "clauseId": clauseId,
-        "forceMajeure": forceMajeure,
-        "penaltyDuration": {
-          "$class": "org.accordproject.time@0.3.0.Duration",
-          "amount": penaltyAmount,
-          "unit": penaltyUnit
-        },
-        "penaltyPercentage": penaltyPercentage,
-        "capPercentage": capPercentage,
-        "termination": {
-          "$class": "org.accordproject.time@0.3.0.Duration",
-          "amount": terminationAmount,
-          "unit": terminationUnit
-        },
-        "fractionalPart": fractionalPart,
-        "_outputFormat": outputFormat
-      }
-
-      const enhancedTemplateData = JSON.stringify(templateData)
-
-      const txResult = await contractTxWithToast(api, activeAccount.address, contract, 'request_draft', {}, [enhancedTemplateData])
+      const txResult = await contractTxWithToast(api, activeAccount.address, contract, 'request_draft', {}, [clauseId, outputFormat])
 
       // Add to transaction history
       setTransactionHistory(prev => [...prev, {
         type: 'request_draft',
         result: {
-          templateData,
+          clauseId,
           outputFormat,
           txHash: txResult.extrinsicHash?.toString(),
           blockHash: txResult.blockHash?.toString(),
@@ -303,15 +259,15 @@ export const LateDeliveryContractInteractions: FC = () => {
     try {
       console.log('🚀 Starting process request...')
 
-      // Convert datetime strings to Unix timestamps (seconds)
-      const agreedDeliveryTimestamp = Math.floor(new Date(agreedDelivery).getTime() / 1000)
-      const deliveredAtTimestamp = deliveredAt ? Math.floor(new Date(deliveredAt).getTime() / 1000) : null
+      // Convert datetime strings to Unix timestamps in milliseconds, as the contract expects
+      const agreedDeliveryTimestamp = new Date(agreedDelivery).getTime()
+      const deliveredAtTimestamp = deliveredAt ? new Date(deliveredAt).getTime() : null
 
       const request = {
         force_majeure: forceMajeure,
         agreed_delivery: agreedDeliveryTimestamp,
         delivered_at: deliveredAtTimestamp ? { Some: deliveredAtTimestamp } : { None: null },
-        goods_value: goodsValue,
+        goods_value: toContractDecimal(goodsValue),
       }
 
       console.log('📝 Request parameters:', {
@@ -447,9 +403,9 @@ export const LateDeliveryContractInteractions: FC = () => {
             })
 
             actualResult = {
-              penalty: penalty?.toString() || 'N/A',
+              penalty: penalty !== undefined ? fromContractDecimal(penalty) : 'N/A',
               buyerMayTerminate: buyerMayTerminate || false,
-              request: request // Include request parameters for display
+              request: { ...request, goods_value: goodsValue } // Include request parameters for display
             }
 
             console.log('✅ Final actualResult:', actualResult)
@@ -493,7 +449,7 @@ export const LateDeliveryContractInteractions: FC = () => {
             force_majeure: request.force_majeure,
             agreed_delivery: request.agreed_delivery,
             delivered_at: request.delivered_at,
-            goods_value: request.goods_value
+            goods_value: goodsValue
           },
           penalty: actualResult?.penalty || (transactionSucceeded ? 'Query failed - check on-chain' : 'Transaction failed'),
           buyerMayTerminate: actualResult?.buyerMayTerminate || false,
@@ -550,8 +506,8 @@ export const LateDeliveryContractInteractions: FC = () => {
         result: {
           request: {
             force_majeure: forceMajeure,
-            agreed_delivery: Math.floor(new Date(agreedDelivery).getTime() / 1000),
-            delivered_at: deliveredAt ? { Some: Math.floor(new Date(deliveredAt).getTime() / 1000) } : { None: null },
+            agreed_delivery: new Date(agreedDelivery).getTime(),
+            delivered_at: deliveredAt ? { Some: new Date(deliveredAt).getTime() } : { None: null },
             goods_value: goodsValue,
           },
           penalty: 'Error',
@@ -776,190 +732,9 @@ export const LateDeliveryContractInteractions: FC = () => {
                       )}
                     />
 
-                    <div className="flex items-center space-x-2">
-                      <input
-                        type="checkbox"
-                        {...requestDraftForm.register('forceMajeure')}
-                        disabled={requestDraftForm.formState.isSubmitting}
-                      />
-                      <FormLabel>Force Majeure</FormLabel>
-                    </div>
-                  </div>
-
-                  {/* Penalty Terms */}
-                  <div className="space-y-3">
-                    <h4 className="text-sm font-semibold text-gray-800 border-b pb-1">Penalty Terms</h4>
-
-                    <div className="grid grid-cols-2 gap-3">
-                      <FormField
-                        control={requestDraftForm.control}
-                        name="penaltyAmount"
-                        render={({ field }) => (
-                          <FormItem>
-                            <FormLabel>Penalty Duration</FormLabel>
-                            <FormControl>
-                              <Input
-                                type="number"
-                                min="1"
-                                placeholder="3"
-                                {...field}
-                                onChange={(e) => field.onChange(Number(e.target.value))}
-                                disabled={requestDraftForm.formState.isSubmitting}
-                              />
-                            </FormControl>
-                            <FormMessage />
-                          </FormItem>
-                        )}
-                      />
-
-                      <FormField
-                        control={requestDraftForm.control}
-                        name="penaltyUnit"
-                        render={({ field }) => (
-                          <FormItem>
-                            <FormLabel>Unit</FormLabel>
-                            <Select onValueChange={field.onChange} defaultValue={field.value}>
-                              <FormControl>
-                                <SelectTrigger disabled={requestDraftForm.formState.isSubmitting}>
-                                  <SelectValue />
-                                </SelectTrigger>
-                              </FormControl>
-                              <SelectContent>
-                                <SelectItem value="days">Days</SelectItem>
-                                <SelectItem value="weeks">Weeks</SelectItem>
-                                <SelectItem value="months">Months</SelectItem>
-                              </SelectContent>
-                            </Select>
-                            <FormMessage />
-                          </FormItem>
-                        )}
-                      />
-                    </div>
-
-                    <div className="grid grid-cols-2 gap-3">
-                      <FormField
-                        control={requestDraftForm.control}
-                        name="penaltyPercentage"
-                        render={({ field }) => (
-                          <FormItem>
-                            <FormLabel>Penalty Percentage (%)</FormLabel>
-                            <FormControl>
-                              <Input
-                                type="number"
-                                step="0.1"
-                                min="0.1"
-                                max="100"
-                                placeholder="10.5"
-                                {...field}
-                                onChange={(e) => field.onChange(Number(e.target.value))}
-                                disabled={requestDraftForm.formState.isSubmitting}
-                              />
-                            </FormControl>
-                            <FormMessage />
-                          </FormItem>
-                        )}
-                      />
-
-                      <FormField
-                        control={requestDraftForm.control}
-                        name="capPercentage"
-                        render={({ field }) => (
-                          <FormItem>
-                            <FormLabel>Cap Percentage (%)</FormLabel>
-                            <FormControl>
-                              <Input
-                                type="number"
-                                step="0.1"
-                                min="0.1"
-                                max="100"
-                                placeholder="55"
-                                {...field}
-                                onChange={(e) => field.onChange(Number(e.target.value))}
-                                disabled={requestDraftForm.formState.isSubmitting}
-                              />
-                            </FormControl>
-                            <FormMessage />
-                          </FormItem>
-                        )}
-                      />
-                    </div>
-                  </div>
-
-                  {/* Termination Terms */}
-                  <div className="space-y-3">
-                    <h4 className="text-sm font-semibold text-gray-800 border-b pb-1">Termination Terms</h4>
-
-                    <div className="grid grid-cols-2 gap-3">
-                      <FormField
-                        control={requestDraftForm.control}
-                        name="terminationAmount"
-                        render={({ field }) => (
-                          <FormItem>
-                            <FormLabel>Termination Period</FormLabel>
-                            <FormControl>
-                              <Input
-                                type="number"
-                                min="1"
-                                placeholder="15"
-                                {...field}
-                                onChange={(e) => field.onChange(Number(e.target.value))}
-                                disabled={requestDraftForm.formState.isSubmitting}
-                              />
-                            </FormControl>
-                            <FormMessage />
-                          </FormItem>
-                        )}
-                      />
-
-                      <FormField
-                        control={requestDraftForm.control}
-                        name="terminationUnit"
-                        render={({ field }) => (
-                          <FormItem>
-                            <FormLabel>Unit</FormLabel>
-                            <Select onValueChange={field.onChange} defaultValue={field.value}>
-                              <FormControl>
-                                <SelectTrigger disabled={requestDraftForm.formState.isSubmitting}>
-                                  <SelectValue />
-                                </SelectTrigger>
-                              </FormControl>
-                              <SelectContent>
-                                <SelectItem value="days">Days</SelectItem>
-                                <SelectItem value="weeks">Weeks</SelectItem>
-                                <SelectItem value="months">Months</SelectItem>
-                              </SelectContent>
-                            </Select>
-                            <FormMessage />
-                          </FormItem>
-                        )}
-                      />
-                    </div>
-
-                    <FormField
-                      control={requestDraftForm.control}
-                      name="fractionalPart"
-                      render={({ field }) => (
-                        <FormItem>
-                          <FormLabel>Fractional Part</FormLabel>
-                          <Select onValueChange={field.onChange} defaultValue={field.value}>
-                            <FormControl>
-                              <SelectTrigger disabled={requestDraftForm.formState.isSubmitting}>
-                                <SelectValue />
-                              </SelectTrigger>
-                            </FormControl>
-                            <SelectContent>
-                              <SelectItem value="days">Days</SelectItem>
-                              <SelectItem value="weeks">Weeks</SelectItem>
-                              <SelectItem value="months">Months</SelectItem>
-                            </SelectContent>
-                          </Select>
-                          <p className="text-xs text-gray-500 mt-1">
-                            Time unit for fractional calculations
-                          </p>
-                          <FormMessage />
-                        </FormItem>
-                      )}
-                    />
+                    <p className="text-xs text-gray-500">
+                      The draft is rendered from the clause&apos;s current terms on the contract
+                    </p>
                   </div>
 
                   {/* Output Format */}
@@ -973,7 +748,7 @@ export const LateDeliveryContractInteractions: FC = () => {
                           <label className="flex items-center space-x-2">
                             <input
                               type="radio"
-                              value="md"
+                              value="Markdown"
                               {...requestDraftForm.register('outputFormat')}
                               disabled={requestDraftForm.formState.isSubmitting}
                             />
@@ -982,7 +757,7 @@ export const LateDeliveryContractInteractions: FC = () => {
                           <label className="flex items-center space-x-2">
                             <input
                               type="radio"
-                              value="pdf"
+                              value="Pdf"
                               {...requestDraftForm.register('outputFormat')}
                               disabled={requestDraftForm.formState.isSubmitting}
                             />
@@ -1027,14 +802,14 @@ export const LateDeliveryContractInteractions: FC = () => {
                     <span>Agreed Delivery:</span>
                     <span className="font-mono text-xs">
                       {processResult.request?.agreed_delivery ?
-                        new Date(processResult.request.agreed_delivery * 1000).toLocaleString() : 'N/A'}
+                        new Date(processResult.request.agreed_delivery).toLocaleString() : 'N/A'}
                     </span>
                   </div>
                   <div className="flex justify-between text-gray-800">
                     <span>Delivered At:</span>
                     <span className="font-mono text-xs">
                       {processResult.request?.delivered_at?.Some ?
-                        new Date(processResult.request.delivered_at.Some * 1000).toLocaleString() :
+                        new Date(processResult.request.delivered_at.Some).toLocaleString() :
                         'Not delivered'}
                     </span>
                   </div>
@@ -1218,14 +993,14 @@ export const LateDeliveryContractInteractions: FC = () => {
                               <span>Agreed Delivery:</span>
                               <span className="font-mono text-xs">
                                 {tx.result.request?.agreed_delivery ?
-                                  new Date(tx.result.request.agreed_delivery * 1000).toLocaleString() : 'N/A'}
+                                  new Date(tx.result.request.agreed_delivery).toLocaleString() : 'N/A'}
                               </span>
                             </div>
                             <div className="flex justify-between text-gray-800">
                               <span>Delivered At:</span>
                               <span className="font-mono text-xs">
                                 {tx.result.request?.delivered_at?.Some ?
-                                  new Date(tx.result.request.delivered_at.Some * 1000).toLocaleString() :
+                                  new Date(tx.result.request.delivered_at.Some).toLocaleString() :
                                   'Not delivered'}
                               </span>
                             </div>
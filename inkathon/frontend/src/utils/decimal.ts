@@ -0,0 +1,25 @@
+// Contract `Decimal` values travel as u128 integers scaled by 10^18
+const DECIMALS = 18
+const SCALE = BigInt(10) ** BigInt(DECIMALS)
+
+/**
+ * Encode a decimal string such as `1000000` or `12.5` as a contract `Decimal`
+ */
+export const toContractDecimal = (value: string): string => {
+  const [whole, fraction = ''] = value.trim().split('.')
+  if (!/^\d*$/.test(whole) || !/^\d*$/.test(fraction) || fraction.length > DECIMALS) {
+    throw new Error(`Invalid decimal: ${value}`)
+  }
+  const raw = BigInt(whole || '0') * SCALE + BigInt(fraction.padEnd(DECIMALS, '0'))
+  return raw.toString()
+}
+
+/**
+ * Decode a contract `Decimal`, as returned raw or with thousands separators, to a decimal string
+ */
+export const fromContractDecimal = (raw: string | number | bigint): string => {
+  const value = BigInt(String(raw).replace(/,/g, ''))
+  const whole = value / SCALE
+  const fraction = (value % SCALE).toString().padStart(DECIMALS, '0').replace(/0+$/, '')
+  return fraction ? `${whole}.${fraction}` : whole.toString()
+}